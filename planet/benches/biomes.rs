@@ -0,0 +1,79 @@
+use {
+    criterion::{black_box, criterion_group, criterion_main, Criterion},
+    crossbeam_channel::Sender,
+    planet::{color::Rgb, BiomeType, World},
+};
+
+const WIDTH: u32 = 400;
+const HEIGHT: u32 = 200;
+const SEED: u32 = 12345;
+
+fn generated_world(progress_sender: &Sender<(f32, String)>) -> World {
+    let mut world = World::new(WIDTH, HEIGHT, SEED);
+    world
+        .generate(progress_sender)
+        .expect("Failed to generate world");
+    world
+}
+
+fn generate_world(c: &mut Criterion) {
+    let (progress_sender, _progress_receiver) = crossbeam_channel::bounded(1);
+
+    _ = c.bench_function("generate 400x200 world", |b| {
+        b.iter(|| black_box(generated_world(&progress_sender)))
+    });
+}
+
+/// Biome stats as they used to be looked up, rebuilt with a heap-allocated
+/// name on every lookup
+struct RebuiltBiomeStats {
+    _name: String,
+    color: Rgb,
+}
+
+fn rebuilt_stats(biome: BiomeType) -> RebuiltBiomeStats {
+    let stats = biome.stats();
+    RebuiltBiomeStats {
+        _name: stats.name.to_string(),
+        color: stats.color,
+    }
+}
+
+// Same lookups as the biome view of the map renderer, before and after the
+// stats were moved to a static table
+fn blend_biome_colors(c: &mut Criterion) {
+    let (progress_sender, _progress_receiver) = crossbeam_channel::bounded(1);
+    let world = generated_world(&progress_sender);
+
+    _ = c.bench_function("blend 400x200 biome colors, rebuilt stats", |b| {
+        b.iter(|| {
+            let mut total = 0.0;
+            for cell in world.terrain.iter().flatten() {
+                for (biome, presence) in cell.biome_presences.iter() {
+                    let color = black_box(rebuilt_stats(*biome)).color;
+                    total += (color.r() + color.g() + color.b()) * presence;
+                }
+            }
+            black_box(total)
+        })
+    });
+    _ = c.bench_function("blend 400x200 biome colors, static table", |b| {
+        b.iter(|| {
+            let mut total = 0.0;
+            for cell in world.terrain.iter().flatten() {
+                for (biome, presence) in cell.biome_presences.iter() {
+                    let color = biome.stats().color;
+                    total += (color.r() + color.g() + color.b()) * presence;
+                }
+            }
+            black_box(total)
+        })
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = generate_world, blend_biome_colors
+}
+criterion_main!(benches);
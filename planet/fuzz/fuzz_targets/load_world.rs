@@ -0,0 +1,21 @@
+//! Feeds arbitrary bytes to the save loader, which must reject them with an
+//! error instead of panicking. Saves from `tests/fixtures` make a good initial
+//! corpus:
+//!
+//! ```sh
+//! mkdir -p fuzz/corpus/load_world && cp tests/fixtures/*.rsplnt fuzz/corpus/load_world
+//! cargo +nightly fuzz run load_world
+//! ```
+
+#![no_main]
+
+use {libfuzzer_sys::fuzz_target, planet::WorldManager};
+
+fuzz_target!(|save: &[u8]| {
+    let mut world_manager = WorldManager::new();
+    if world_manager.load_world_from_bytes(save).is_ok() {
+        // Whatever loads must be usable
+        let world = world_manager.get_world().unwrap();
+        _ = world.coast_distances();
+    }
+});
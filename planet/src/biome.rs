@@ -1,18 +1,23 @@
-use crate::{macros::iterable_enum, World};
-#[cfg(feature = "render")]
-use bevy::prelude::Color;
+use crate::{color::Rgb, macros::iterable_enum, World};
 
 #[derive(Debug, Clone, Default)]
 pub struct BiomeStats {
-    pub name:            String,
-    #[cfg(feature = "render")]
-    pub color:           Color,
-    pub min_altitude:    f32,
-    pub max_altitude:    f32,
-    pub min_rainfall:    f32,
-    pub max_rainfall:    f32,
-    pub min_temperature: f32,
-    pub max_temperature: f32,
+    pub name:               &'static str,
+    pub color:              Rgb,
+    pub min_altitude:       f32,
+    pub max_altitude:       f32,
+    pub min_rainfall:       f32,
+    pub max_rainfall:       f32,
+    pub min_temperature:    f32,
+    pub max_temperature:    f32,
+    pub min_slope:          f32,
+    pub max_slope:          f32,
+    pub min_depth:          f32,
+    pub max_depth:          f32,
+    pub min_coast_distance: u32,
+    pub max_coast_distance: u32,
+    /// How fertile the soil of this biome is, from 0 to 1
+    pub fertility:          f32,
 }
 
 iterable_enum!(BiomeType {
@@ -23,106 +28,343 @@ iterable_enum!(BiomeType {
     Taiga,
     Tundra,
     Desert,
-    Rainforest
+    Rainforest,
+    Alpine,
+    Mountain,
+    Savanna,
+    Steppe,
+    Wetland,
+    Mangrove,
+    ShallowSea,
+    DeepOcean,
+    CoralReef,
 });
 
-impl From<BiomeType> for BiomeStats {
-    fn from(biome_type: BiomeType) -> BiomeStats {
-        match biome_type {
-            BiomeType::IceCap => BiomeStats {
-                name: "Ice Cap".into(),
-                #[cfg(feature = "render")]
-                color: Color::WHITE,
-                min_altitude: World::MIN_ALTITUDE,
-                max_altitude: World::MAX_ALTITUDE,
-                min_rainfall: World::MIN_RAINFALL,
-                max_rainfall: World::MAX_RAINFALL,
-                min_temperature: World::MIN_TEMPERATURE,
-                max_temperature: -15.0,
-            },
-            BiomeType::Ocean => BiomeStats {
-                name: "Ocean".into(),
-                #[cfg(feature = "render")]
-                color: Color::rgb_u8(28, 66, 84),
-                min_altitude: World::MIN_ALTITUDE,
-                max_altitude: 0.0,
-                min_rainfall: World::MIN_RAINFALL,
-                max_rainfall: World::MAX_RAINFALL,
-                min_temperature: -15.0,
-                max_temperature: World::MAX_TEMPERATURE,
-            },
-            BiomeType::Grassland => BiomeStats {
-                name: "Grassland".into(),
-                #[cfg(feature = "render")]
-                color: Color::rgb_u8(167, 177, 84),
-                min_altitude: 0.0,
-                max_altitude: World::MAX_ALTITUDE,
-                min_rainfall: 15.0,
-                max_rainfall: 1575.0,
-                min_temperature: -5.0,
-                max_temperature: World::MAX_TEMPERATURE,
-            },
-            BiomeType::Forest => BiomeStats {
-                name: "Forest".into(),
-                #[cfg(feature = "render")]
-                color: Color::rgb_u8(76, 132, 55),
-                min_altitude: 0.0,
-                max_altitude: World::MAX_ALTITUDE,
-                min_rainfall: 1375.0,
-                max_rainfall: 2975.0,
-                min_temperature: -5.0,
-                max_temperature: World::MAX_TEMPERATURE,
-            },
-            BiomeType::Taiga => BiomeStats {
-                name: "Taiga".into(),
-                #[cfg(feature = "render")]
-                color: Color::rgb_u8(43, 63, 40),
-                min_altitude: 0.0,
-                max_altitude: World::MAX_ALTITUDE,
-                min_rainfall: 475.0,
-                max_rainfall: World::MAX_RAINFALL,
-                min_temperature: -15.0,
-                max_temperature: -0.0,
-            },
-            BiomeType::Tundra => BiomeStats {
-                name: "Tundra ".into(),
-                #[cfg(feature = "render")]
-                color: Color::rgb_u8(139, 139, 128),
-                min_altitude: 0.0,
-                max_altitude: World::MAX_ALTITUDE,
-                min_rainfall: World::MIN_RAINFALL,
-                max_rainfall: 725.0,
-                min_temperature: -20.0,
-                max_temperature: -0.0,
-            },
-            BiomeType::Desert => BiomeStats {
-                name: "Desert ".into(),
-                #[cfg(feature = "render")]
-                color: Color::rgb_u8(253, 225, 171),
-                min_altitude: 0.0,
-                max_altitude: World::MAX_ALTITUDE,
-                min_rainfall: World::MIN_RAINFALL,
-                max_rainfall: 275.0,
-                min_temperature: -5.0,
-                max_temperature: World::MAX_TEMPERATURE,
-            },
-            BiomeType::Rainforest => BiomeStats {
-                name: "Rainforest".into(),
-                #[cfg(feature = "render")]
-                color: Color::rgb_u8(59, 103, 43),
-                min_altitude: 0.0,
-                max_altitude: World::MAX_ALTITUDE,
-                min_rainfall: 1775.0,
-                max_rainfall: World::MAX_RAINFALL,
-                min_temperature: -5.0,
-                max_temperature: World::MAX_TEMPERATURE,
-            },
-        }
+impl BiomeType {
+    #[must_use]
+    pub fn stats(self) -> &'static BiomeStats {
+        &BIOME_STATS[self as usize]
     }
 }
 
-impl From<&BiomeType> for BiomeStats {
-    fn from(biome_type: &BiomeType) -> BiomeStats {
-        (*biome_type).into()
+impl From<BiomeType> for &'static BiomeStats {
+    fn from(biome_type: BiomeType) -> &'static BiomeStats {
+        biome_type.stats()
     }
 }
+
+impl From<&BiomeType> for &'static BiomeStats {
+    fn from(biome_type: &BiomeType) -> &'static BiomeStats {
+        biome_type.stats()
+    }
+}
+
+// Must be in the same order as the BiomeType variants
+static BIOME_STATS: [BiomeStats; BiomeType::ITEM_COUNT] = [
+    // BiomeType::IceCap
+    BiomeStats {
+        name:               "Ice Cap",
+        color:              Rgb::WHITE,
+        min_altitude:       World::MIN_ALTITUDE,
+        max_altitude:       World::MAX_ALTITUDE,
+        min_rainfall:       World::MIN_RAINFALL,
+        max_rainfall:       World::MAX_RAINFALL,
+        min_temperature:    World::MIN_TEMPERATURE,
+        max_temperature:    -15.0,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.0,
+    },
+    // BiomeType::Ocean
+    BiomeStats {
+        name:               "Ocean",
+        color:              Rgb::new(28.0 / 255.0, 66.0 / 255.0, 84.0 / 255.0),
+        min_altitude:       World::MIN_ALTITUDE,
+        max_altitude:       0.0,
+        min_rainfall:       World::MIN_RAINFALL,
+        max_rainfall:       World::MAX_RAINFALL,
+        min_temperature:    -15.0,
+        max_temperature:    World::MAX_TEMPERATURE,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          500.0,
+        max_depth:          7000.0,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.0,
+    },
+    // BiomeType::Grassland
+    BiomeStats {
+        name:               "Grassland",
+        color:              Rgb::new(167.0 / 255.0, 177.0 / 255.0, 84.0 / 255.0),
+        min_altitude:       0.0,
+        max_altitude:       World::MAX_ALTITUDE,
+        min_rainfall:       15.0,
+        max_rainfall:       1575.0,
+        min_temperature:    -5.0,
+        max_temperature:    World::MAX_TEMPERATURE,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.9,
+    },
+    // BiomeType::Forest
+    BiomeStats {
+        name:               "Forest",
+        color:              Rgb::new(76.0 / 255.0, 132.0 / 255.0, 55.0 / 255.0),
+        min_altitude:       0.0,
+        max_altitude:       World::MAX_ALTITUDE,
+        min_rainfall:       1375.0,
+        max_rainfall:       2975.0,
+        min_temperature:    -5.0,
+        max_temperature:    World::MAX_TEMPERATURE,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.7,
+    },
+    // BiomeType::Taiga
+    BiomeStats {
+        name:               "Taiga",
+        color:              Rgb::new(43.0 / 255.0, 63.0 / 255.0, 40.0 / 255.0),
+        min_altitude:       0.0,
+        max_altitude:       World::MAX_ALTITUDE,
+        min_rainfall:       475.0,
+        max_rainfall:       World::MAX_RAINFALL,
+        min_temperature:    -15.0,
+        max_temperature:    -0.0,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.3,
+    },
+    // BiomeType::Tundra
+    BiomeStats {
+        name:               "Tundra",
+        color:              Rgb::new(139.0 / 255.0, 139.0 / 255.0, 128.0 / 255.0),
+        min_altitude:       0.0,
+        max_altitude:       World::MAX_ALTITUDE,
+        min_rainfall:       World::MIN_RAINFALL,
+        max_rainfall:       725.0,
+        min_temperature:    -20.0,
+        max_temperature:    -0.0,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.1,
+    },
+    // BiomeType::Desert
+    BiomeStats {
+        name:               "Desert",
+        color:              Rgb::new(253.0 / 255.0, 225.0 / 255.0, 171.0 / 255.0),
+        min_altitude:       0.0,
+        max_altitude:       World::MAX_ALTITUDE,
+        min_rainfall:       World::MIN_RAINFALL,
+        max_rainfall:       275.0,
+        min_temperature:    -5.0,
+        max_temperature:    World::MAX_TEMPERATURE,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.1,
+    },
+    // BiomeType::Rainforest
+    BiomeStats {
+        name:               "Rainforest",
+        color:              Rgb::new(59.0 / 255.0, 103.0 / 255.0, 43.0 / 255.0),
+        min_altitude:       0.0,
+        max_altitude:       World::MAX_ALTITUDE,
+        min_rainfall:       1775.0,
+        max_rainfall:       World::MAX_RAINFALL,
+        min_temperature:    -5.0,
+        max_temperature:    World::MAX_TEMPERATURE,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.4,
+    },
+    // BiomeType::Alpine
+    BiomeStats {
+        name:               "Alpine",
+        color:              Rgb::new(176.0 / 255.0, 170.0 / 255.0, 150.0 / 255.0),
+        min_altitude:       2500.0,
+        max_altitude:       9000.0,
+        min_rainfall:       World::MIN_RAINFALL,
+        max_rainfall:       World::MAX_RAINFALL,
+        min_temperature:    -20.0,
+        max_temperature:    10.0,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.2,
+    },
+    // BiomeType::Mountain
+    BiomeStats {
+        name:               "Mountain",
+        color:              Rgb::new(120.0 / 255.0, 104.0 / 255.0, 92.0 / 255.0),
+        min_altitude:       1000.0,
+        max_altitude:       9000.0,
+        min_rainfall:       World::MIN_RAINFALL,
+        max_rainfall:       World::MAX_RAINFALL,
+        min_temperature:    World::MIN_TEMPERATURE,
+        max_temperature:    World::MAX_TEMPERATURE,
+        min_slope:          150.0,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.1,
+    },
+    // BiomeType::Savanna
+    BiomeStats {
+        name:               "Savanna",
+        color:              Rgb::new(196.0 / 255.0, 178.0 / 255.0, 92.0 / 255.0),
+        min_altitude:       0.0,
+        max_altitude:       4000.0,
+        min_rainfall:       400.0,
+        max_rainfall:       1700.0,
+        min_temperature:    15.0,
+        max_temperature:    World::MAX_TEMPERATURE,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.5,
+    },
+    // BiomeType::Steppe
+    BiomeStats {
+        name:               "Steppe",
+        color:              Rgb::new(190.0 / 255.0, 186.0 / 255.0, 130.0 / 255.0),
+        min_altitude:       0.0,
+        max_altitude:       5000.0,
+        min_rainfall:       150.0,
+        max_rainfall:       700.0,
+        min_temperature:    -10.0,
+        max_temperature:    20.0,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.7,
+    },
+    // BiomeType::Wetland
+    BiomeStats {
+        name:               "Wetland",
+        color:              Rgb::new(86.0 / 255.0, 120.0 / 255.0, 96.0 / 255.0),
+        min_altitude:       0.0,
+        max_altitude:       600.0,
+        min_rainfall:       1200.0,
+        max_rainfall:       World::MAX_RAINFALL,
+        min_temperature:    -5.0,
+        max_temperature:    World::MAX_TEMPERATURE,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          40.0,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.8,
+    },
+    // BiomeType::Mangrove
+    BiomeStats {
+        name:               "Mangrove",
+        color:              Rgb::new(56.0 / 255.0, 94.0 / 255.0, 72.0 / 255.0),
+        min_altitude:       0.0,
+        max_altitude:       300.0,
+        min_rainfall:       1500.0,
+        max_rainfall:       World::MAX_RAINFALL,
+        min_temperature:    22.0,
+        max_temperature:    World::MAX_TEMPERATURE,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: 1,
+        fertility:          0.3,
+    },
+    // BiomeType::ShallowSea
+    BiomeStats {
+        name:               "Shallow Sea",
+        color:              Rgb::new(56.0 / 255.0, 110.0 / 255.0, 135.0 / 255.0),
+        min_altitude:       World::MIN_ALTITUDE,
+        max_altitude:       0.0,
+        min_rainfall:       World::MIN_RAINFALL,
+        max_rainfall:       World::MAX_RAINFALL,
+        min_temperature:    -15.0,
+        max_temperature:    World::MAX_TEMPERATURE,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          1000.0,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.0,
+    },
+    // BiomeType::DeepOcean
+    BiomeStats {
+        name:               "Deep Ocean",
+        color:              Rgb::new(16.0 / 255.0, 40.0 / 255.0, 58.0 / 255.0),
+        min_altitude:       World::MIN_ALTITUDE,
+        max_altitude:       0.0,
+        min_rainfall:       World::MIN_RAINFALL,
+        max_rainfall:       World::MAX_RAINFALL,
+        min_temperature:    -15.0,
+        max_temperature:    World::MAX_TEMPERATURE,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          6000.0,
+        max_depth:          World::MAX_DEPTH,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: World::MAX_COAST_DISTANCE,
+        fertility:          0.0,
+    },
+    // BiomeType::CoralReef
+    BiomeStats {
+        name:               "Coral Reef",
+        color:              Rgb::new(70.0 / 255.0, 160.0 / 255.0, 160.0 / 255.0),
+        min_altitude:       -1000.0,
+        max_altitude:       0.0,
+        min_rainfall:       World::MIN_RAINFALL,
+        max_rainfall:       World::MAX_RAINFALL,
+        min_temperature:    22.0,
+        max_temperature:    World::MAX_TEMPERATURE,
+        min_slope:          World::MIN_SLOPE,
+        max_slope:          World::MAX_SLOPE,
+        min_depth:          World::MIN_DEPTH,
+        max_depth:          300.0,
+        min_coast_distance: World::MIN_COAST_DISTANCE,
+        max_coast_distance: 2,
+        fertility:          0.0,
+    },
+];
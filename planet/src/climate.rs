@@ -0,0 +1,145 @@
+use crate::{color::Rgb, macros::iterable_enum};
+
+// Only the classes that can be derived from annual values are listed. The
+// seasonal precipitation subtypes (Cs*, Cw*, Ds*, Dw*) need monthly rainfall,
+// which the world doesn't simulate.
+iterable_enum!(KoppenClass {
+    Af,
+    Am,
+    Aw,
+    BWh,
+    BWk,
+    BSh,
+    BSk,
+    Cfa,
+    Cfb,
+    Cfc,
+    Dfa,
+    Dfb,
+    Dfc,
+    Dfd,
+    ET,
+    EF,
+});
+
+impl KoppenClass {
+    /// Classifies a land cell by its annual mean temperature (°C), annual
+    /// rainfall (mm) and latitude (radians, 0 at the equator).
+    ///
+    /// The world only stores annual values, so the seasonal values Köppen
+    /// needs are approximated:
+    /// - The warmest and coldest month deviate from the annual mean by an
+    ///   amplitude that grows from 1°C at the equator to 15°C at the poles.
+    /// - Rainfall is assumed to be spread evenly enough that the driest month
+    ///   receives half of the mean monthly rainfall.
+    /// - The aridity threshold uses the variant for rainfall without a dominant
+    ///   season.
+    #[must_use]
+    pub fn classify(temperature: f32, rainfall: f32, latitude: f32) -> KoppenClass {
+        let amplitude = 1.0 + 14.0 * f32::abs(f32::sin(latitude));
+        let warmest_month = temperature + amplitude;
+        let coldest_month = temperature - amplitude;
+
+        let aridity_threshold = 20.0 * temperature + 140.0;
+        if rainfall < aridity_threshold {
+            let hot = temperature >= 18.0;
+            return match (rainfall < aridity_threshold / 2.0, hot) {
+                (true, true) => KoppenClass::BWh,
+                (true, false) => KoppenClass::BWk,
+                (false, true) => KoppenClass::BSh,
+                (false, false) => KoppenClass::BSk,
+            };
+        }
+
+        if warmest_month < 10.0 {
+            return if warmest_month >= 0.0 {
+                KoppenClass::ET
+            } else {
+                KoppenClass::EF
+            };
+        }
+
+        if coldest_month >= 18.0 {
+            let driest_month = rainfall / 24.0;
+            return if driest_month >= 60.0 {
+                KoppenClass::Af
+            } else if driest_month >= 100.0 - rainfall / 25.0 {
+                KoppenClass::Am
+            } else {
+                KoppenClass::Aw
+            };
+        }
+
+        // Fraction of a sinusoidal year that is warmer than 10°C
+        let warm_months = 12.0 * f32::acos(f32::clamp((10.0 - temperature) / amplitude, -1.0, 1.0))
+            / std::f32::consts::PI;
+
+        if coldest_month > 0.0 {
+            if warmest_month >= 22.0 {
+                KoppenClass::Cfa
+            } else if warm_months >= 4.0 {
+                KoppenClass::Cfb
+            } else {
+                KoppenClass::Cfc
+            }
+        } else if warmest_month >= 22.0 {
+            KoppenClass::Dfa
+        } else if warm_months >= 4.0 {
+            KoppenClass::Dfb
+        } else if coldest_month >= -38.0 {
+            KoppenClass::Dfc
+        } else {
+            KoppenClass::Dfd
+        }
+    }
+
+    #[must_use]
+    pub fn code(self) -> &'static str {
+        self.into()
+    }
+
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            KoppenClass::Af => "Tropical rainforest",
+            KoppenClass::Am => "Tropical monsoon",
+            KoppenClass::Aw => "Tropical savanna",
+            KoppenClass::BWh => "Hot desert",
+            KoppenClass::BWk => "Cold desert",
+            KoppenClass::BSh => "Hot semi-arid",
+            KoppenClass::BSk => "Cold semi-arid",
+            KoppenClass::Cfa => "Humid subtropical",
+            KoppenClass::Cfb => "Temperate oceanic",
+            KoppenClass::Cfc => "Subpolar oceanic",
+            KoppenClass::Dfa => "Hot-summer humid continental",
+            KoppenClass::Dfb => "Warm-summer humid continental",
+            KoppenClass::Dfc => "Subarctic",
+            KoppenClass::Dfd => "Extremely cold subarctic",
+            KoppenClass::ET => "Tundra",
+            KoppenClass::EF => "Ice cap",
+        }
+    }
+
+    /// Colours of the commonly used Köppen-Geiger map legend
+    #[must_use]
+    pub fn color(self) -> Rgb {
+        match self {
+            KoppenClass::Af => Rgb::rgb_u8(0, 0, 255),
+            KoppenClass::Am => Rgb::rgb_u8(0, 120, 255),
+            KoppenClass::Aw => Rgb::rgb_u8(70, 170, 250),
+            KoppenClass::BWh => Rgb::rgb_u8(255, 0, 0),
+            KoppenClass::BWk => Rgb::rgb_u8(255, 150, 150),
+            KoppenClass::BSh => Rgb::rgb_u8(245, 165, 0),
+            KoppenClass::BSk => Rgb::rgb_u8(255, 220, 100),
+            KoppenClass::Cfa => Rgb::rgb_u8(200, 255, 80),
+            KoppenClass::Cfb => Rgb::rgb_u8(100, 255, 80),
+            KoppenClass::Cfc => Rgb::rgb_u8(50, 200, 0),
+            KoppenClass::Dfa => Rgb::rgb_u8(0, 255, 255),
+            KoppenClass::Dfb => Rgb::rgb_u8(55, 200, 255),
+            KoppenClass::Dfc => Rgb::rgb_u8(0, 125, 125),
+            KoppenClass::Dfd => Rgb::rgb_u8(0, 70, 95),
+            KoppenClass::ET => Rgb::rgb_u8(178, 178, 178),
+            KoppenClass::EF => Rgb::rgb_u8(102, 102, 102),
+        }
+    }
+}
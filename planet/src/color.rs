@@ -0,0 +1,86 @@
+#[cfg(feature = "render")]
+use bevy::prelude::Color;
+use {
+    serde::{Deserialize, Serialize},
+    std::ops::{Add, Mul},
+};
+
+/// An opaque sRGB colour with components from 0 to 1.
+///
+/// Map colours are computed with this instead of bevy's `Color`, which is only
+/// available with the `render` feature, so maps can be drawn without a GPU.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Rgb {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb::new(0.0, 0.0, 0.0);
+    pub const BLUE: Rgb = Rgb::new(0.0, 0.0, 1.0);
+    pub const WHITE: Rgb = Rgb::new(1.0, 1.0, 1.0);
+
+    #[must_use]
+    pub const fn new(r: f32, g: f32, b: f32) -> Rgb {
+        Rgb { r, g, b }
+    }
+
+    #[must_use]
+    pub fn rgb_u8(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb::new(
+            f32::from(r) / 255.0,
+            f32::from(g) / 255.0,
+            f32::from(b) / 255.0,
+        )
+    }
+
+    #[must_use]
+    pub fn r(self) -> f32 {
+        self.r
+    }
+
+    #[must_use]
+    pub fn g(self) -> f32 {
+        self.g
+    }
+
+    #[must_use]
+    pub fn b(self) -> f32 {
+        self.b
+    }
+
+    /// The colour as 8 bit components, clamped to the displayable range
+    #[must_use]
+    pub fn as_rgb_u8(self) -> [u8; 3] {
+        [self.r, self.g, self.b].map(|component| (component.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    #[must_use]
+    pub fn as_rgba_f32(self) -> [f32; 4] {
+        [self.r, self.g, self.b, 1.0]
+    }
+}
+
+impl Add for Rgb {
+    type Output = Rgb;
+
+    fn add(self, rhs: Rgb) -> Rgb {
+        Rgb::new(self.r + rhs.r, self.g + rhs.g, self.b + rhs.b)
+    }
+}
+
+impl Mul<f32> for Rgb {
+    type Output = Rgb;
+
+    fn mul(self, rhs: f32) -> Rgb {
+        Rgb::new(self.r * rhs, self.g * rhs, self.b * rhs)
+    }
+}
+
+#[cfg(feature = "render")]
+impl From<Rgb> for Color {
+    fn from(color: Rgb) -> Color {
+        Color::rgb(color.r, color.g, color.b)
+    }
+}
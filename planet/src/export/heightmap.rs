@@ -0,0 +1,82 @@
+use {
+    crate::{export::ExportError, macros::iterable_enum, World},
+    std::io::Write,
+};
+
+iterable_enum!(HeightmapFormat { Png, R16 });
+
+impl HeightmapFormat {
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            HeightmapFormat::Png => "png",
+            HeightmapFormat::R16 => "r16",
+        }
+    }
+
+    /// Description of the file layout, for the export metadata
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            HeightmapFormat::Png => "16-bit grayscale PNG",
+            HeightmapFormat::R16 => "raw 16-bit unsigned little-endian samples, no header",
+        }
+    }
+}
+
+/// Maps an altitude linearly from `World::MIN_ALTITUDE..=World::MAX_ALTITUDE`
+/// to the full range of a 16-bit sample
+#[must_use]
+pub fn altitude_to_sample(altitude: f32) -> u16 {
+    let value = (altitude - World::MIN_ALTITUDE) / World::ALTITUDE_SPAN;
+    (value.clamp(0.0, 1.0) * f32::from(u16::MAX)).round() as u16
+}
+
+/// Inverse of [`altitude_to_sample`]
+#[must_use]
+pub fn sample_to_altitude(sample: u16) -> f32 {
+    World::MIN_ALTITUDE + World::ALTITUDE_SPAN * f32::from(sample) / f32::from(u16::MAX)
+}
+
+/// Altitude samples of the whole world, row by row from the north edge to the
+/// south edge
+#[must_use]
+pub fn heightmap_samples(world: &World) -> Vec<u16> {
+    world
+        .terrain
+        .iter()
+        .rev()
+        .flatten()
+        .map(|cell| altitude_to_sample(cell.altitude))
+        .collect()
+}
+
+pub fn write_heightmap<W: Write>(
+    world: &World,
+    format: HeightmapFormat,
+    mut writer: W,
+) -> Result<(), ExportError> {
+    let samples = heightmap_samples(world);
+    match format {
+        HeightmapFormat::Png => {
+            let mut encoder = png::Encoder::new(writer, world.width, world.height);
+            encoder.set_color(png::ColorType::Grayscale);
+            encoder.set_depth(png::BitDepth::Sixteen);
+            let mut writer = encoder.write_header()?;
+            // PNG stores 16-bit samples big-endian
+            let bytes: Vec<u8> = samples
+                .iter()
+                .flat_map(|sample| sample.to_be_bytes())
+                .collect();
+            writer.write_image_data(&bytes)?;
+            Ok(())
+        },
+        HeightmapFormat::R16 => {
+            let bytes: Vec<u8> = samples
+                .iter()
+                .flat_map(|sample| sample.to_le_bytes())
+                .collect();
+            writer.write_all(&bytes).map_err(ExportError::FailedToWrite)
+        },
+    }
+}
@@ -0,0 +1,115 @@
+use {
+    crate::{
+        export::ExportError,
+        map::{self, MapStyle, WorldOverlay, WorldView},
+        saving::atomic,
+        World,
+    },
+    bevy::utils::HashSet,
+    std::{
+        io::{BufWriter, Write},
+        path::Path,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapImageOptions {
+    pub view:     WorldView,
+    pub overlays: HashSet<WorldOverlay>,
+    pub style:    MapStyle,
+    /// Size of a cell in pixels, 1 for the world's native resolution
+    pub scale:    u32,
+}
+impl Default for MapImageOptions {
+    fn default() -> Self {
+        MapImageOptions {
+            view:     WorldView::default(),
+            overlays: HashSet::default(),
+            style:    MapStyle::default(),
+            scale:    1,
+        }
+    }
+}
+
+impl MapImageOptions {
+    pub const EXTENSION: &'static str = "png";
+
+    /// Size of the image in pixels. Fails if the scale is 0, or so large that
+    /// the image's pixels couldn't be addressed.
+    pub fn image_size(&self, world: &World) -> Result<(u32, u32), ExportError> {
+        let invalid_scale = || ExportError::InvalidScale(self.scale);
+        if self.scale == 0 {
+            return Err(invalid_scale());
+        }
+        let width = world
+            .width
+            .checked_mul(self.scale)
+            .ok_or_else(invalid_scale)?;
+        let height = world
+            .height
+            .checked_mul(self.scale)
+            .ok_or_else(invalid_scale)?;
+        _ = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .ok_or_else(invalid_scale)?;
+        Ok((width, height))
+    }
+}
+
+/// 8-bit RGB pixels of the map, north edge first, each cell scaled up to a
+/// `scale`x`scale` square
+pub fn map_pixels(world: &World, options: &MapImageOptions) -> Result<Vec<u8>, ExportError> {
+    let (image_width, image_height) = options.image_size(world)?;
+    let scale = options.scale as usize;
+    let row_length = image_width as usize * 3;
+
+    let mut pixels = Vec::with_capacity(row_length * image_height as usize);
+    let colors: Vec<[u8; 3]> =
+        map::map_colors(world, options.view, &options.overlays, &options.style)
+            .map(|color| color.as_rgb_u8())
+            .collect();
+    for cells in colors.chunks(world.width as usize) {
+        let row_start = pixels.len();
+        for color in cells {
+            for _ in 0..scale {
+                pixels.extend_from_slice(color);
+            }
+        }
+        for _ in 1..scale {
+            pixels.extend_from_within(row_start..row_start + row_length);
+        }
+    }
+    Ok(pixels)
+}
+
+pub fn write_map_png<W: Write>(
+    world: &World,
+    options: &MapImageOptions,
+    writer: W,
+) -> Result<(), ExportError> {
+    let (image_width, image_height) = options.image_size(world)?;
+    let pixels = map_pixels(world, options)?;
+
+    let mut encoder = png::Encoder::new(writer, image_width, image_height);
+    encoder.set_color(png::ColorType::RGB);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels)?;
+    Ok(())
+}
+
+/// Writes the map to `path`, leaving an existing file untouched if the map
+/// can't be exported
+pub fn export_map_png<P: AsRef<Path>>(
+    world: &World,
+    options: &MapImageOptions,
+    path: P,
+) -> Result<(), ExportError> {
+    _ = options.image_size(world)?;
+    atomic::write_atomically_with(path, |file| {
+        let mut writer = BufWriter::new(file);
+        write_map_png(world, options, &mut writer)?;
+        writer.flush().map_err(ExportError::FailedToWrite)
+    })
+}
@@ -0,0 +1,90 @@
+use {
+    crate::{export::raster::DataLayer, saving::recipe::GENERATOR_VERSION, World},
+    serde::{Deserialize, Serialize},
+};
+
+/// How the cells of exported layers map to the globe. Rows go from the north
+/// edge to the south edge, columns from west to east, and the map wraps
+/// around east to west.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GridProjection {
+    /// Every cell spans the same number of degrees of latitude and longitude
+    Equirectangular {
+        north: f32,
+        south: f32,
+        west:  f32,
+        east:  f32,
+    },
+}
+impl Default for GridProjection {
+    fn default() -> Self {
+        GridProjection::Equirectangular {
+            north: 90.0,
+            south: -90.0,
+            west:  -180.0,
+            east:  180.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerMetadata {
+    pub layer:     DataLayer,
+    /// File name, relative to the metadata file
+    pub file:      String,
+    pub format:    String,
+    pub unit:      String,
+    /// Range of values that the lowest and highest possible sample stand for
+    pub min:       f32,
+    pub max:       f32,
+    /// Range of the values actually present in this world
+    pub world_min: f32,
+    pub world_max: f32,
+}
+impl LayerMetadata {
+    #[must_use]
+    pub fn new(world: &World, layer: DataLayer, file: String, format: &str) -> LayerMetadata {
+        let (min, max) = layer.range();
+        let (world_min, world_max) = layer.world_range(world);
+        LayerMetadata {
+            layer,
+            file,
+            format: String::from(format),
+            unit: String::from(layer.unit()),
+            min,
+            max,
+            world_min,
+            world_max,
+        }
+    }
+}
+
+/// Sidecar file describing a set of exported layers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportMetadata {
+    pub generator_version: String,
+    pub seed:              u32,
+    pub width:             u32,
+    pub height:            u32,
+    pub projection:        GridProjection,
+    pub layers:            Vec<LayerMetadata>,
+}
+impl ExportMetadata {
+    pub const EXTENSION: &'static str = "meta.ron";
+
+    #[must_use]
+    pub fn new(world: &World) -> ExportMetadata {
+        ExportMetadata {
+            generator_version: String::from(GENERATOR_VERSION),
+            seed:              world.seed,
+            width:             world.width,
+            height:            world.height,
+            projection:        GridProjection::default(),
+            layers:            vec![],
+        }
+    }
+
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+}
@@ -0,0 +1,68 @@
+pub mod heightmap;
+pub use heightmap::HeightmapFormat;
+pub mod map_image;
+pub use map_image::MapImageOptions;
+pub mod metadata;
+pub use metadata::{ExportMetadata, GridProjection};
+pub mod raster;
+pub use raster::DataLayer;
+use std::{error::Error, fmt::Display, io};
+
+#[derive(Debug)]
+pub enum ExportError {
+    MissingWorld,
+    InvalidScale(u32),
+    EncodingError(png::EncodingError),
+    MetadataSerializationError(ron::Error),
+    FailedToWrite(io::Error),
+}
+impl Error for ExportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExportError::MissingWorld => None,
+            ExportError::InvalidScale(_) => None,
+            ExportError::EncodingError(error) => Some(error),
+            ExportError::MetadataSerializationError(error) => Some(error),
+            ExportError::FailedToWrite(error) => Some(error),
+        }
+    }
+
+    fn description(&self) -> &str {
+        "description() is deprecated; use Display"
+    }
+
+    fn cause(&self) -> Option<&dyn Error> {
+        self.source()
+    }
+}
+impl Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ExportError::MissingWorld => f.write_str("No world to export found."),
+            ExportError::InvalidScale(scale) => f.write_fmt(format_args!(
+                "Invalid image scale {scale}, must be at least 1 and small enough for the image \
+                 to fit in memory."
+            )),
+            ExportError::EncodingError(err) => {
+                f.write_fmt(format_args!("Failed to encode image - {err}"))
+            },
+            ExportError::MetadataSerializationError(_) => {
+                f.write_str("Failed to serialize export metadata.")
+            },
+            ExportError::FailedToWrite(_) => f.write_str("Failed to write exported file."),
+        }
+    }
+}
+impl From<png::EncodingError> for ExportError {
+    fn from(error: png::EncodingError) -> Self {
+        match error {
+            png::EncodingError::IoError(error) => ExportError::FailedToWrite(error),
+            error => ExportError::EncodingError(error),
+        }
+    }
+}
+impl From<io::Error> for ExportError {
+    fn from(error: io::Error) -> Self {
+        ExportError::FailedToWrite(error)
+    }
+}
@@ -0,0 +1,94 @@
+use {
+    crate::{macros::iterable_enum, TerrainCell, World},
+    std::io::{self, Write},
+};
+
+iterable_enum!(DataLayer {
+    Altitude,
+    Rainfall,
+    Temperature
+});
+
+impl DataLayer {
+    #[must_use]
+    pub fn value(self, cell: &TerrainCell) -> f32 {
+        match self {
+            DataLayer::Altitude => cell.altitude,
+            DataLayer::Rainfall => cell.rainfall,
+            DataLayer::Temperature => cell.temperature,
+        }
+    }
+
+    /// Range any world's values fall in
+    #[must_use]
+    pub fn range(self) -> (f32, f32) {
+        match self {
+            DataLayer::Altitude => (World::MIN_ALTITUDE, World::MAX_ALTITUDE),
+            DataLayer::Rainfall => (World::MIN_RAINFALL, World::MAX_RAINFALL),
+            DataLayer::Temperature => (World::MIN_TEMPERATURE, World::MAX_TEMPERATURE),
+        }
+    }
+
+    /// Range of the values in the given world
+    #[must_use]
+    pub fn world_range(self, world: &World) -> (f32, f32) {
+        match self {
+            DataLayer::Altitude => (world.min_altitude, world.max_altitude),
+            DataLayer::Rainfall => (world.min_rainfall, world.max_rainfall),
+            DataLayer::Temperature => (world.min_temperature, world.max_temperature),
+        }
+    }
+
+    #[must_use]
+    pub fn unit(self) -> &'static str {
+        match self {
+            DataLayer::Altitude => "m",
+            DataLayer::Rainfall => "mm/year",
+            DataLayer::Temperature => "°C",
+        }
+    }
+
+    /// Used in the names of exported files
+    #[must_use]
+    pub fn file_suffix(self) -> &'static str {
+        match self {
+            DataLayer::Altitude => "altitude",
+            DataLayer::Rainfall => "rainfall",
+            DataLayer::Temperature => "temperature",
+        }
+    }
+}
+
+pub const NPY_EXTENSION: &str = "npy";
+pub const NPY_DESCRIPTION: &str = "NumPy array of little-endian 32-bit floats";
+
+/// Writes a layer as a NumPy `.npy` file holding a `(height, width)` array of
+/// `float32`, with the first row at the north edge.
+///
+/// See <https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html>
+pub fn write_npy<W: Write>(world: &World, layer: DataLayer, mut writer: W) -> io::Result<()> {
+    const MAGIC: &[u8] = b"\x93NUMPY\x01\x00";
+    const ALIGNMENT: usize = 64;
+
+    let mut header = format!(
+        "{{'descr': '<f4', 'fortran_order': False, 'shape': ({}, {}), }}",
+        world.height, world.width
+    );
+    // The header is padded with spaces and ends with a newline, so the data
+    // starts aligned
+    let unpadded_length = MAGIC.len() + 2 + header.len() + 1;
+    let padding = (ALIGNMENT - unpadded_length % ALIGNMENT) % ALIGNMENT;
+    header.push_str(&" ".repeat(padding));
+    header.push('\n');
+
+    let mut bytes = Vec::with_capacity(
+        MAGIC.len() + 2 + header.len() + (world.width * world.height) as usize * 4,
+    );
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    for cell in world.terrain.iter().rev().flatten() {
+        bytes.extend_from_slice(&layer.value(cell).to_le_bytes());
+    }
+    writer.write_all(&bytes)
+}
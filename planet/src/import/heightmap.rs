@@ -0,0 +1,166 @@
+use {
+    crate::{import::ImportError, World},
+    std::{fs, io::Read, path::Path},
+};
+
+/// How heightmap samples turn into altitudes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeightmapOptions {
+    /// Sample value at altitude 0, from 0 for black to 1 for white
+    pub sea_level:      f32,
+    /// Altitude difference between black and white, in meters
+    pub altitude_scale: f32,
+}
+impl Default for HeightmapOptions {
+    /// Reads heightmaps as exported by [`crate::export::heightmap`]
+    fn default() -> Self {
+        HeightmapOptions {
+            sea_level:      -World::MIN_ALTITUDE / World::ALTITUDE_SPAN,
+            altitude_scale: World::ALTITUDE_SPAN,
+        }
+    }
+}
+impl HeightmapOptions {
+    #[must_use]
+    pub fn altitude(&self, sample: f32) -> f32 {
+        ((sample - self.sea_level) * self.altitude_scale)
+            .clamp(World::MIN_ALTITUDE, World::MAX_ALTITUDE)
+    }
+}
+
+/// Grayscale image used as the altitude of a new world, see
+/// [`World::generate_from_heightmap`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heightmap {
+    pub width:   u32,
+    pub height:  u32,
+    /// Samples from 0 to 1, row by row from the north edge to the south edge
+    pub samples: Vec<f32>,
+}
+
+impl Heightmap {
+    /// Extensions of raw heightmaps, which have no header and need their size
+    /// given separately
+    pub const RAW_EXTENSIONS: [&'static str; 2] = ["r16", "raw"];
+
+    /// Reads an 8 or 16-bit PNG. Colour images are averaged to grayscale, and
+    /// alpha is ignored.
+    pub fn from_png<R: Read>(reader: R) -> Result<Heightmap, ImportError> {
+        let mut decoder = png::Decoder::new(reader);
+        decoder.set_transformations(png::Transformations::EXPAND);
+        let (info, mut reader) = decoder.read_info()?;
+        let mut bytes = vec![0; info.buffer_size()];
+        reader.next_frame(&mut bytes)?;
+
+        let (samples, max_value): (Vec<u16>, f32) = match info.bit_depth {
+            png::BitDepth::Eight => (bytes.iter().map(|&byte| u16::from(byte)).collect(), 255.0),
+            png::BitDepth::Sixteen => (
+                bytes
+                    .chunks_exact(2)
+                    .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+                    .collect(),
+                f32::from(u16::MAX),
+            ),
+            bit_depth => return Err(ImportError::UnsupportedImage(info.color_type, bit_depth)),
+        };
+        let (channels, colors) = match info.color_type {
+            png::ColorType::Grayscale => (1, 1),
+            png::ColorType::GrayscaleAlpha => (2, 1),
+            png::ColorType::RGB => (3, 3),
+            png::ColorType::RGBA => (4, 3),
+            color_type => return Err(ImportError::UnsupportedImage(color_type, info.bit_depth)),
+        };
+
+        Ok(Heightmap {
+            width:   info.width,
+            height:  info.height,
+            samples: samples
+                .chunks_exact(channels)
+                .map(|pixel| {
+                    pixel[..colors].iter().map(|&value| f32::from(value)).sum::<f32>()
+                        / (colors as f32 * max_value)
+                })
+                .collect(),
+        })
+    }
+
+    /// Reads headerless samples, either 16-bit little-endian like `.r16`
+    /// files, or 8-bit, depending on the length
+    pub fn from_raw(bytes: &[u8], width: u32, height: u32) -> Result<Heightmap, ImportError> {
+        let size = width as usize * height as usize;
+        let samples = if bytes.len() == size * 2 {
+            bytes
+                .chunks_exact(2)
+                .map(|bytes| f32::from(u16::from_le_bytes([bytes[0], bytes[1]])) / f32::from(u16::MAX))
+                .collect()
+        } else if bytes.len() == size {
+            bytes.iter().map(|&byte| f32::from(byte) / 255.0).collect()
+        } else {
+            return Err(ImportError::InvalidRawSize(bytes.len()));
+        };
+
+        Ok(Heightmap {
+            width,
+            height,
+            samples,
+        })
+    }
+
+    /// Reads a PNG, or a raw heightmap of the given size
+    pub fn open<P: AsRef<Path>>(
+        path: P,
+        raw_width: u32,
+        raw_height: u32,
+    ) -> Result<Heightmap, ImportError> {
+        let path = path.as_ref();
+        let is_raw = path.extension().is_some_and(|extension| {
+            Heightmap::RAW_EXTENSIONS
+                .iter()
+                .any(|raw| extension.eq_ignore_ascii_case(raw))
+        });
+        if is_raw {
+            let bytes = fs::read(path).map_err(ImportError::FailedToRead)?;
+            Heightmap::from_raw(&bytes, raw_width, raw_height)
+        } else {
+            Heightmap::from_png(fs::File::open(path).map_err(ImportError::FailedToRead)?)
+        }
+    }
+
+    /// Sample at a point given in cells, interpolated between the four
+    /// nearest samples. Wraps around east to west like the world does.
+    #[must_use]
+    pub fn sample(&self, x: f32, y: f32) -> f32 {
+        let width = self.width as usize;
+        let height = self.height as usize;
+
+        let x = x.rem_euclid(self.width as f32);
+        let y = y.clamp(0.0, (height - 1) as f32);
+        let (x0, y0) = (x.floor() as usize % width, y.floor() as usize);
+        let (x1, y1) = ((x0 + 1) % width, usize::min(y0 + 1, height - 1));
+        let (tx, ty) = (x.fract(), y.fract());
+
+        let at = |x: usize, y: usize| self.samples[y * width + x];
+        let north = at(x0, y0) * (1.0 - tx) + at(x1, y0) * tx;
+        let south = at(x0, y1) * (1.0 - tx) + at(x1, y1) * tx;
+        north * (1.0 - ty) + south * ty
+    }
+
+    /// Sample for a cell of a world of the given size, resampling the
+    /// heightmap if its size differs. `y` counts from the south edge, like
+    /// [`World::terrain`] rows.
+    #[must_use]
+    pub fn world_sample(&self, x: usize, y: usize, world_width: u32, world_height: u32) -> f32 {
+        let north_y = world_height as usize - 1 - y;
+        if (self.width, self.height) == (world_width, world_height) {
+            return self.samples[north_y * self.width as usize + x];
+        }
+
+        // Sample at the centre of the world cell
+        let scale_x = self.width as f32 / world_width as f32;
+        let scale_y = self.height as f32 / world_height as f32;
+        self.sample(
+            (x as f32 + 0.5) * scale_x - 0.5,
+            (north_y as f32 + 0.5) * scale_y - 0.5,
+        )
+    }
+}
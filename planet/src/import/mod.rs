@@ -0,0 +1,79 @@
+pub mod heightmap;
+pub use heightmap::{Heightmap, HeightmapOptions};
+pub mod plnt;
+pub use plnt::PlntImportReport;
+
+use {
+    crate::{WorldGenError, WorldValidationError},
+    std::{error::Error, fmt::Display, io},
+};
+
+#[derive(Debug)]
+pub enum ImportError {
+    FailedToRead(io::Error),
+    DecodingError(png::DecodingError),
+    UnsupportedImage(png::ColorType, png::BitDepth),
+    InvalidRawSize(usize),
+    InvalidXml(roxmltree::Error),
+    InvalidPlnt(String),
+    GenerationError(WorldGenError),
+    InvalidWorld(WorldValidationError),
+}
+impl Error for ImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImportError::FailedToRead(error) => Some(error),
+            ImportError::DecodingError(error) => Some(error),
+            ImportError::UnsupportedImage(_, _) => None,
+            ImportError::InvalidRawSize(_) => None,
+            ImportError::InvalidXml(error) => Some(error),
+            ImportError::InvalidPlnt(_) => None,
+            ImportError::GenerationError(error) => Some(error),
+            ImportError::InvalidWorld(error) => Some(error),
+        }
+    }
+
+    fn description(&self) -> &str {
+        "description() is deprecated; use Display"
+    }
+
+    fn cause(&self) -> Option<&dyn Error> {
+        self.source()
+    }
+}
+impl Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ImportError::FailedToRead(_) => f.write_str("Failed to read imported file."),
+            ImportError::DecodingError(err) => {
+                f.write_fmt(format_args!("Failed to decode image - {err}"))
+            },
+            ImportError::UnsupportedImage(color_type, bit_depth) => f.write_fmt(format_args!(
+                "Unsupported image format ({color_type:?}, {bit_depth:?})"
+            )),
+            ImportError::InvalidRawSize(length) => f.write_fmt(format_args!(
+                "Raw heightmap of {length} bytes doesn't match the given size"
+            )),
+            ImportError::InvalidXml(err) => {
+                f.write_fmt(format_args!("Imported file is not valid XML - {err}"))
+            },
+            ImportError::InvalidPlnt(reason) => f.write_fmt(format_args!(
+                "Imported file is not a valid Worlds History Sim save - {reason}"
+            )),
+            ImportError::GenerationError(err) => {
+                f.write_fmt(format_args!("Failed to generate imported world - {err}"))
+            },
+            ImportError::InvalidWorld(err) => {
+                f.write_fmt(format_args!("Imported world is inconsistent - {err}"))
+            },
+        }
+    }
+}
+impl From<png::DecodingError> for ImportError {
+    fn from(error: png::DecodingError) -> Self {
+        match error {
+            png::DecodingError::IoError(error) => ImportError::FailedToRead(error),
+            error => ImportError::DecodingError(error),
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! Reads `.plnt` saves of the original C# Worlds History Sim.
+//!
+//! Those are `XmlSerializer` output, with the world's size and seed as
+//! attributes of the root `World` element, and each `TerrainCell` storing its
+//! position as `Longitude`/`Latitude` indices. Cells use the same value ranges
+//! as this version, so altitude, rainfall and temperature are copied as they
+//! are. Everything else is counted in a [`PlntImportReport`].
+//!
+//! Cells missing from a save are taken from the world generated from its seed,
+//! and cells without any known biome get the biomes their terrain supports.
+
+use {
+    crate::{import::ImportError, macros::iterable_enum, BiomeType, World},
+    roxmltree::{Document, Node},
+    std::{collections::BTreeMap, fmt::Display, str::FromStr},
+};
+
+pub const EXTENSION: &str = "plnt";
+/// Largest world that is imported. Far larger than the worlds of the original,
+/// but keeps a corrupted size from allocating more terrain than fits in memory.
+pub const MAX_WORLD_SIZE: (u32, u32) = (4096, 2048);
+
+const WORLD_ELEMENT: &str = "World";
+const CELL_ELEMENT: &str = "TerrainCell";
+const WORLD_ATTRIBUTES: [&str; 3] = ["Width", "Height", "Seed"];
+const CELL_ATTRIBUTES: [&str; 6] = [
+    "Longitude",
+    "Latitude",
+    "Altitude",
+    "Rainfall",
+    "Temperature",
+    "Arability",
+];
+const BIOME_NAMES_ELEMENT: &str = "PresentBiomeNames";
+const BIOME_PRESENCES_ELEMENT: &str = "BiomePresences";
+
+iterable_enum!(GenerationStage {
+    Terrain,
+    Biomes,
+    Arability,
+    Resources
+});
+
+/// What could not be carried over from a `.plnt` save
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlntImportReport {
+    /// Attributes (`Element@Attribute`) and elements (`Parent/Element`) that
+    /// have no counterpart in this version, with how often they occurred
+    pub unmapped:            BTreeMap<String, usize>,
+    /// Biome names that don't match any [`BiomeType`], with how often they
+    /// occurred
+    pub unknown_biomes:      BTreeMap<String, usize>,
+    /// Cells of the world that the save had no data for, generated from the
+    /// seed
+    pub missing_cells:       usize,
+    /// The save stores no terrain, so it was generated from the seed instead
+    pub regenerated_terrain: bool,
+    /// Generation stages run because the save has no data for them
+    pub generated:           Vec<GenerationStage>,
+}
+impl PlntImportReport {
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.unmapped.is_empty()
+            && self.unknown_biomes.is_empty()
+            && self.missing_cells == 0
+            && !self.regenerated_terrain
+    }
+
+    fn add_unmapped(&mut self, name: String) {
+        *self.unmapped.entry(name).or_default() += 1;
+    }
+}
+impl Display for PlntImportReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.regenerated_terrain {
+            f.write_str(
+                "The save doesn't contain terrain, it was generated from the seed and may differ \
+                 from the original\n",
+            )?;
+        }
+        if self.missing_cells > 0 {
+            f.write_fmt(format_args!(
+                "{} terrain cells were missing from the save\n",
+                self.missing_cells
+            ))?;
+        }
+        if !self.generated.is_empty() {
+            let stages: Vec<&'static str> = self.generated.iter().map(Into::into).collect();
+            f.write_fmt(format_args!("Generated {}\n", stages.join(", ")))?;
+        }
+        for (biome, count) in &self.unknown_biomes {
+            f.write_fmt(format_args!("Unknown biome '{biome}' in {count} cells\n"))?;
+        }
+        for (name, count) in &self.unmapped {
+            f.write_fmt(format_args!("Not imported: {name} ({count}x)\n"))?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_attribute<T: FromStr>(node: Node, name: &'static str) -> Result<Option<T>, ImportError> {
+    match node.attribute(name) {
+        None => Ok(None),
+        Some(value) => match value.trim().parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(ImportError::InvalidPlnt(format!(
+                "Invalid {}@{name} '{value}'",
+                node.tag_name().name()
+            ))),
+        },
+    }
+}
+
+fn require_attribute<T: FromStr>(node: Node, name: &'static str) -> Result<T, ImportError> {
+    parse_attribute(node, name)?.ok_or_else(|| {
+        ImportError::InvalidPlnt(format!("Missing {}@{name}", node.tag_name().name()))
+    })
+}
+
+/// Matches biome names ignoring case, spaces and underscores, so both
+/// display names ("Ice Cap") and ids ("ice_cap") are found
+fn find_biome(name: &str) -> Option<BiomeType> {
+    let normalize = |name: &str| -> String {
+        name.chars()
+            .filter(|char| char.is_alphanumeric())
+            .map(|char| char.to_ascii_lowercase())
+            .collect()
+    };
+    let name = normalize(name);
+    BiomeType::iterator()
+        .copied()
+        .find(|biome| normalize(biome.stats().name) == name)
+}
+
+fn element_children<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(Node::is_element)
+}
+
+/// Positions of the cells a `.plnt` save has too little data for
+#[derive(Default)]
+struct BlankCells {
+    missing:        Vec<(usize, usize)>,
+    without_biomes: Vec<(usize, usize)>,
+}
+
+/// Reads a world from the contents of a `.plnt` save, generating what the save
+/// has no data for
+pub fn import_plnt(xml: &str) -> Result<(World, PlntImportReport), ImportError> {
+    let (mut world, report, blank_cells) = read_plnt(xml)?;
+    complete_plnt_world(&mut world, &report, &blank_cells)?;
+    world.validate().map_err(ImportError::InvalidWorld)?;
+    Ok((world, report))
+}
+
+fn read_plnt(xml: &str) -> Result<(World, PlntImportReport, BlankCells), ImportError> {
+    let document = Document::parse(xml).map_err(ImportError::InvalidXml)?;
+    let root = document.root_element();
+    if root.tag_name().name() != WORLD_ELEMENT {
+        return Err(ImportError::InvalidPlnt(format!(
+            "Root element is {}, not {WORLD_ELEMENT}",
+            root.tag_name().name()
+        )));
+    }
+
+    let width: u32 = require_attribute(root, "Width")?;
+    let height: u32 = require_attribute(root, "Height")?;
+    // C# stores the seed as a signed int
+    let seed: i64 = require_attribute(root, "Seed")?;
+    if width == 0 || height == 0 {
+        return Err(ImportError::InvalidPlnt(format!(
+            "Invalid world size {width}x{height}"
+        )));
+    }
+    let (max_width, max_height) = MAX_WORLD_SIZE;
+    if width > max_width || height > max_height {
+        return Err(ImportError::InvalidPlnt(format!(
+            "World size {width}x{height} is larger than the largest importable world, \
+             {max_width}x{max_height}"
+        )));
+    }
+
+    let mut report = PlntImportReport::default();
+    let mut world = World::new(width, height, seed as u32);
+
+    for attribute in root.attributes() {
+        if !WORLD_ATTRIBUTES.contains(&attribute.name()) {
+            report.add_unmapped(format!("{WORLD_ELEMENT}@{}", attribute.name()));
+        }
+    }
+    for child in element_children(root) {
+        let has_cells = child
+            .descendants()
+            .any(|node| node.tag_name().name() == CELL_ELEMENT);
+        if !has_cells {
+            report.add_unmapped(format!("{WORLD_ELEMENT}/{}", child.tag_name().name()));
+        }
+    }
+
+    let mut imported = vec![vec![false; width as usize]; height as usize];
+    let mut blank_cells = BlankCells::default();
+    let mut has_arability = false;
+    let mut has_biomes = false;
+    for cell_node in root
+        .descendants()
+        .filter(|node| node.tag_name().name() == CELL_ELEMENT)
+    {
+        let x: usize = require_attribute(cell_node, "Longitude")?;
+        let y: usize = require_attribute(cell_node, "Latitude")?;
+        if x >= width as usize || y >= height as usize {
+            return Err(ImportError::InvalidPlnt(format!(
+                "{CELL_ELEMENT} at {x}, {y} is outside of the world"
+            )));
+        }
+        imported[y][x] = true;
+
+        let cell = &mut world.terrain[y][x];
+        cell.altitude = require_attribute(cell_node, "Altitude")?;
+        cell.rainfall = require_attribute(cell_node, "Rainfall")?;
+        cell.temperature = require_attribute(cell_node, "Temperature")?;
+        if let Some(arability) = parse_attribute(cell_node, "Arability")? {
+            cell.arability = arability;
+            has_arability = true;
+        }
+        for attribute in cell_node.attributes() {
+            if !CELL_ATTRIBUTES.contains(&attribute.name()) {
+                report.add_unmapped(format!("{CELL_ELEMENT}@{}", attribute.name()));
+            }
+        }
+
+        let mut biome_names = vec![];
+        let mut biome_presences = vec![];
+        for child in element_children(cell_node) {
+            match child.tag_name().name() {
+                BIOME_NAMES_ELEMENT => {
+                    biome_names
+                        .extend(element_children(child).map(|name| name.text().unwrap_or("")));
+                },
+                BIOME_PRESENCES_ELEMENT => {
+                    for presence in element_children(child) {
+                        let text = presence.text().unwrap_or("").trim();
+                        biome_presences.push(text.parse::<f32>().map_err(|_| {
+                            ImportError::InvalidPlnt(format!("Invalid biome presence '{text}'"))
+                        })?);
+                    }
+                },
+                name => report.add_unmapped(format!("{CELL_ELEMENT}/{name}")),
+            }
+        }
+        if biome_names.len() != biome_presences.len() {
+            return Err(ImportError::InvalidPlnt(format!(
+                "{CELL_ELEMENT} at {x}, {y} has {} biome names but {} biome presences",
+                biome_names.len(),
+                biome_presences.len()
+            )));
+        }
+        let mut dropped_biome = false;
+        for (name, presence) in biome_names.into_iter().zip(biome_presences) {
+            has_biomes = true;
+            match find_biome(name) {
+                Some(biome) => cell.biome_presences.push((biome, presence)),
+                None => {
+                    *report.unknown_biomes.entry(String::from(name)).or_default() += 1;
+                    dropped_biome = true;
+                },
+            }
+        }
+        // The known biomes take the place of the unknown ones
+        let total: f32 = cell
+            .biome_presences
+            .iter()
+            .map(|(_, presence)| presence)
+            .sum();
+        if dropped_biome && total > 0.0 {
+            for (_, presence) in &mut cell.biome_presences {
+                *presence /= total;
+            }
+        }
+        if cell.biome_presences.is_empty() {
+            blank_cells.without_biomes.push((x, y));
+        }
+    }
+
+    for (y, row) in imported.iter().enumerate() {
+        for (x, imported) in row.iter().enumerate() {
+            if !imported {
+                blank_cells.missing.push((x, y));
+            }
+        }
+    }
+    report.missing_cells = blank_cells.missing.len();
+    report.regenerated_terrain = report.missing_cells == width as usize * height as usize;
+    if !report.regenerated_terrain {
+        if report.missing_cells > 0 {
+            report.generated.push(GenerationStage::Terrain);
+        }
+        // Cells without biomes would be left blank on the map, unless no cell
+        // has any and all of them are generated
+        if !has_biomes {
+            blank_cells.without_biomes.clear();
+            report.generated.push(GenerationStage::Biomes);
+        } else if !blank_cells.without_biomes.is_empty() {
+            report.generated.push(GenerationStage::Biomes);
+        }
+        if !has_arability {
+            report.generated.push(GenerationStage::Arability);
+        }
+        // The original has no natural resources
+        report.generated.push(GenerationStage::Resources);
+    }
+
+    Ok((world, report, blank_cells))
+}
+
+/// Runs the generation stages listed in the report, or generates the whole
+/// world if the save had no terrain
+fn complete_plnt_world(
+    world: &mut World,
+    report: &PlntImportReport,
+    blank_cells: &BlankCells,
+) -> Result<(), ImportError> {
+    // Nobody listens to the progress of an import
+    let (progress_sender, _) = crossbeam_channel::bounded(0);
+    if report.regenerated_terrain {
+        return world
+            .generate(&progress_sender)
+            .map_err(ImportError::GenerationError);
+    }
+
+    if report.generated.contains(&GenerationStage::Terrain) {
+        let mut generated = World::new(world.width, world.height, world.seed);
+        generated
+            .generate(&progress_sender)
+            .map_err(ImportError::GenerationError)?;
+        for &(x, y) in &blank_cells.missing {
+            world.terrain[y][x] = generated.terrain[y][x].clone();
+        }
+    }
+
+    world.complete_terrain();
+    world.validate().map_err(ImportError::InvalidWorld)?;
+    let coast_distances = world.coast_distances();
+    for stage in &report.generated {
+        match stage {
+            GenerationStage::Terrain => {},
+            // Only the cells left without biomes, if the save has any
+            GenerationStage::Biomes if !blank_cells.without_biomes.is_empty() => {
+                for &(x, y) in &blank_cells.without_biomes {
+                    world.generate_cell_biomes(x, y, coast_distances[y][x]);
+                }
+            },
+            GenerationStage::Biomes => world.generate_biomes(&progress_sender, &coast_distances),
+            GenerationStage::Arability => {
+                world.generate_arability(&progress_sender, &coast_distances)
+            },
+            GenerationStage::Resources => world.generate_resources(&progress_sender),
+        }
+    }
+    Ok(())
+}
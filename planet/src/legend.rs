@@ -0,0 +1,212 @@
+//! What the colours of the map mean, for the view and overlays it is drawn
+//! with.
+
+use {
+    crate::{
+        color::Rgb,
+        map::{self, MapStyle, WorldOverlay, WorldView},
+        BiomeType,
+        KoppenClass,
+        ResourceType,
+        World,
+    },
+    bevy::utils::HashSet,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegendSection {
+    pub title:   &'static str,
+    pub content: LegendContent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LegendContent {
+    /// Colours and what they stand for
+    Swatches(Vec<(String, Rgb)>),
+    /// Colours along a continuous scale, from its lowest to its highest value
+    Ramp {
+        colors: Vec<Rgb>,
+        ticks:  Vec<LegendTick>,
+    },
+    /// A line drawn at every multiple of an interval
+    Contour { color: Rgb, label: String },
+}
+
+/// A labelled value on a ramp
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegendTick {
+    /// From 0 at the lowest value of the ramp to 1 at its highest
+    pub position: f32,
+    pub label:    String,
+}
+
+/// Colours sampled along each ramp
+pub const RAMP_STEPS: usize = 64;
+/// Ticks on a ramp are placed at round values, about this many of them
+const TARGET_TICKS: f32 = 5.0;
+
+/// Legend of the view, followed by that of each visible overlay
+#[must_use]
+pub fn legend(
+    world: &World,
+    view: WorldView,
+    overlays: &HashSet<WorldOverlay>,
+    style: &MapStyle,
+) -> Vec<LegendSection> {
+    let mut sections = vec![view_legend(world, view, style)];
+    // Overlays are listed in a fixed order, as the set has none
+    for overlay in WorldOverlay::iterator() {
+        if overlays.contains(overlay) {
+            sections.push(overlay_legend(*overlay, style));
+        }
+    }
+    sections
+}
+
+fn view_legend(world: &World, view: WorldView, style: &MapStyle) -> LegendSection {
+    let colors = &style.colors;
+    let content = match view {
+        WorldView::Biomes => LegendContent::Swatches(
+            BiomeType::iterator()
+                .map(|biome| (biome.stats().name.to_string(), colors.biomes.color(*biome)))
+                .collect(),
+        ),
+        WorldView::Topography => ramp(world.min_altitude, world.max_altitude, "m", |altitude| {
+            map::altitude_contour_color(world, altitude)
+        }),
+        WorldView::Coastlines => {
+            let palette = map::coastline_palette(&colors.coastlines);
+            LegendContent::Swatches(vec![
+                (String::from("Water"), palette[0]),
+                (String::from("Land"), palette[1]),
+                (String::from("Steep land"), palette[4]),
+                (String::from("Near the coast"), palette[3]),
+                (String::from("Coastline"), palette[2]),
+            ])
+        },
+        WorldView::Climate => LegendContent::Swatches(
+            KoppenClass::iterator()
+                .map(|class| (<&'static str>::from(class).to_string(), class.color()))
+                .chain([(String::from("Water"), colors.biomes.color(BiomeType::Ocean))])
+                .collect(),
+        ),
+        WorldView::Relief => LegendContent::Ramp {
+            colors: (0..RAMP_STEPS)
+                .map(|step| Rgb::WHITE * (step as f32 / (RAMP_STEPS - 1) as f32))
+                .collect(),
+            ticks:  vec![
+                LegendTick {
+                    position: 0.0,
+                    label:    String::from("In shadow"),
+                },
+                LegendTick {
+                    position: 1.0,
+                    label:    String::from("Facing the light"),
+                },
+            ],
+        },
+    };
+    LegendSection {
+        title: view.label(),
+        content,
+    }
+}
+
+fn overlay_legend(overlay: WorldOverlay, style: &MapStyle) -> LegendSection {
+    let contours = &style.contours;
+    let colors = &style.colors;
+    let content = match overlay {
+        WorldOverlay::Temperature => ramp(
+            World::MIN_TEMPERATURE,
+            World::MAX_TEMPERATURE,
+            "°C",
+            |temperature| map::temperature_color(temperature, &colors.temperature),
+        ),
+        WorldOverlay::Rainfall => {
+            ramp(World::MIN_RAINFALL, World::MAX_RAINFALL, "mm", |rainfall| {
+                map::rainfall_color(rainfall, &colors.rainfall)
+            })
+        },
+        WorldOverlay::Arability => ramp(0.0, 1.0, "", |arability| {
+            map::arability_color(arability, &colors.arability)
+        }),
+        WorldOverlay::Resources => LegendContent::Swatches(
+            ResourceType::iterator()
+                .map(|resource| {
+                    let stats = resource.stats();
+                    (stats.name.to_string(), stats.color)
+                })
+                .collect(),
+        ),
+        WorldOverlay::AltitudeContours => LegendContent::Contour {
+            color: map::ALTITUDE_CONTOUR_COLOR,
+            label: format!("Every {} m", contours.altitude),
+        },
+        WorldOverlay::RainfallContours => LegendContent::Contour {
+            color: map::RAINFALL_CONTOUR_COLOR,
+            label: format!("Every {} mm", contours.rainfall),
+        },
+        WorldOverlay::TemperatureContours => LegendContent::Contour {
+            color: map::TEMPERATURE_CONTOUR_COLOR,
+            label: format!("Every {} °C", contours.temperature),
+        },
+    };
+    LegendSection {
+        title: overlay.label(),
+        content,
+    }
+}
+
+/// Ramp of the colours from `min` to `max`, with ticks at round values
+fn ramp(min: f32, max: f32, unit: &str, color: impl Fn(f32) -> Rgb) -> LegendContent {
+    let span = max - min;
+    let colors = (0..RAMP_STEPS)
+        .map(|step| color(min + span * step as f32 / (RAMP_STEPS - 1) as f32))
+        .collect();
+
+    let ticks = if span > 0.0 {
+        let step = tick_step(span);
+        let first = (min / step).ceil() as i64;
+        let last = (max / step).floor() as i64;
+        (first..=last)
+            .map(|tick| {
+                let value = tick as f32 * step;
+                LegendTick {
+                    position: (value - min) / span,
+                    label:    format_value(value, step, unit),
+                }
+            })
+            .collect()
+    } else {
+        vec![LegendTick {
+            position: 0.0,
+            label:    format_value(min, 1.0, unit),
+        }]
+    };
+    LegendContent::Ramp { colors, ticks }
+}
+
+/// Round distance between ticks, 1, 2 or 5 times a power of 10
+fn tick_step(span: f32) -> f32 {
+    let rough = span / TARGET_TICKS;
+    let magnitude = 10.0_f32.powf(rough.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= rough)
+        .unwrap_or(10.0 * magnitude)
+}
+
+fn format_value(value: f32, step: f32, unit: &str) -> String {
+    let decimals = if step >= 1.0 {
+        0
+    } else {
+        (-step.log10().floor()) as usize
+    };
+    let value = format!("{value:.decimals$}");
+    if unit.is_empty() {
+        value
+    } else {
+        format!("{value} {unit}")
+    }
+}
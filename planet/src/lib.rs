@@ -3,9 +3,22 @@ pub mod world;
 pub use world::{TerrainCell, World, WorldGenError};
 pub mod biome;
 pub use biome::{BiomeStats, BiomeType};
+pub mod climate;
+pub use climate::KoppenClass;
+pub mod color;
+pub mod export;
+pub mod import;
+pub mod resource;
+pub use resource::{ResourceStats, ResourceType};
 pub mod world_manager;
 pub use world_manager::WorldManager;
 pub mod macros;
+pub mod legend;
+pub mod map;
+pub mod palette;
+pub mod projection;
 pub mod math_util;
 pub mod perlin;
 pub mod saving;
+pub mod validation;
+pub use validation::WorldValidationError;
@@ -1,9 +1,10 @@
 macro_rules! iterable_enum {
-    ($Name:ident { $($Variant:ident),*$(,)? }) =>
+    ($(#[$meta:meta])* $Name:ident { $($(#[$variant_meta:meta])* $Variant:ident),*$(,)? }) =>
     {
         #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, serde::Deserialize, serde::Serialize)]
+        $(#[$meta])*
         pub enum $Name {
-            $($Variant),*,
+            $($(#[$variant_meta])* $Variant),*,
         }
         impl $Name {
             pub const ITEMS: &'static [$Name] = &[$($Name::$Variant),*];
@@ -0,0 +1,524 @@
+use {
+    crate::{
+        color::Rgb,
+        macros::iterable_enum,
+        palette::{BiomePalette, ColorRamp},
+        projection::Projection,
+        BiomeType,
+        TerrainCell,
+        World,
+    },
+    bevy::{math::Vec3, utils::HashSet},
+    serde::{Deserialize, Serialize},
+};
+
+iterable_enum!(
+    #[derive(Default)]
+    WorldView {
+        #[default]
+        Biomes,
+        Topography,
+        Coastlines,
+        Climate,
+        Relief
+    }
+);
+iterable_enum!(WorldOverlay {
+    Temperature,
+    Rainfall,
+    Arability,
+    Resources,
+    AltitudeContours,
+    RainfallContours,
+    TemperatureContours
+});
+
+impl WorldView {
+    /// Name of the view as shown to the player
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            WorldView::Biomes => "Biomes",
+            WorldView::Topography => "Topography",
+            WorldView::Coastlines => "Coastlines",
+            WorldView::Climate => "Climate",
+            WorldView::Relief => "Relief",
+        }
+    }
+}
+
+impl WorldOverlay {
+    /// Name of the overlay as shown to the player
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            WorldOverlay::Temperature => "Temperature",
+            WorldOverlay::Rainfall => "Rainfall",
+            WorldOverlay::Arability => "Arability",
+            WorldOverlay::Resources => "Resources",
+            WorldOverlay::AltitudeContours => "Altitude contours",
+            WorldOverlay::RainfallContours => "Rainfall contours",
+            WorldOverlay::TemperatureContours => "Temperature contours",
+        }
+    }
+
+    /// Whether the overlay draws lines over the view instead of being blended
+    /// with it
+    #[must_use]
+    pub fn is_contour(self) -> bool {
+        matches!(
+            self,
+            WorldOverlay::AltitudeContours
+                | WorldOverlay::RainfallContours
+                | WorldOverlay::TemperatureContours
+        )
+    }
+}
+
+/// Shading of the terrain by a distant light, from the altitude gradients.
+/// Drawn on its own as the [`WorldView::Relief`] view, and multiplied over
+/// any view if `multiply` is set.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Hillshade {
+    /// Direction the light comes from, in degrees clockwise from north
+    pub azimuth:      f32,
+    /// Angle of the light above the horizon, in degrees
+    pub elevation:    f32,
+    /// Factor altitudes are multiplied by, as cells are far wider than
+    /// mountains are high
+    pub exaggeration: f32,
+    pub multiply:     bool,
+}
+impl Default for Hillshade {
+    fn default() -> Self {
+        Hillshade {
+            azimuth:      315.0,
+            elevation:    45.0,
+            exaggeration: 20.0,
+            multiply:     false,
+        }
+    }
+}
+
+/// Values between the lines of the contour overlays
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ContourIntervals {
+    /// In metres
+    pub altitude:    f32,
+    /// In millimetres a year
+    pub rainfall:    f32,
+    /// In degrees Celsius
+    pub temperature: f32,
+}
+impl Default for ContourIntervals {
+    fn default() -> Self {
+        ContourIntervals {
+            altitude:    500.0,
+            rainfall:    250.0,
+            temperature: 5.0,
+        }
+    }
+}
+
+/// Colours the views and overlays are drawn with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapColors {
+    pub biomes:      BiomePalette,
+    pub coastlines:  ColorRamp,
+    pub temperature: ColorRamp,
+    pub rainfall:    ColorRamp,
+    pub arability:   ColorRamp,
+}
+impl Default for MapColors {
+    fn default() -> Self {
+        MapColors {
+            biomes:      BiomePalette::ColorBlindSafe,
+            coastlines:  ColorRamp::Classic,
+            temperature: ColorRamp::Classic,
+            rainfall:    ColorRamp::Classic,
+            arability:   ColorRamp::Classic,
+        }
+    }
+}
+impl MapColors {
+    /// Colours of an overlay drawn along a continuous scale
+    #[must_use]
+    pub fn overlay_ramp(&self, overlay: WorldOverlay) -> Option<&ColorRamp> {
+        match overlay {
+            WorldOverlay::Temperature => Some(&self.temperature),
+            WorldOverlay::Rainfall => Some(&self.rainfall),
+            WorldOverlay::Arability => Some(&self.arability),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn overlay_ramp_mut(&mut self, overlay: WorldOverlay) -> Option<&mut ColorRamp> {
+        match overlay {
+            WorldOverlay::Temperature => Some(&mut self.temperature),
+            WorldOverlay::Rainfall => Some(&mut self.rainfall),
+            WorldOverlay::Arability => Some(&mut self.arability),
+            _ => None,
+        }
+    }
+}
+
+/// How the map is drawn, besides the view and overlays
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MapStyle {
+    pub hillshade: Hillshade,
+    pub contours:  ContourIntervals,
+    pub colors:    MapColors,
+}
+
+impl Hillshade {
+    /// Distance around the equator in metres, as on Earth
+    const CIRCUMFERENCE: f32 = 40_000_000.0;
+
+    /// Brightness of a cell lit by the light, from 0 for slopes facing away
+    /// from it to 1 for slopes facing it
+    #[must_use]
+    pub fn shade(&self, world: &World, cell: &TerrainCell) -> f32 {
+        let width = world.width as usize;
+        let height = world.height as usize;
+        let (x, y) = (cell.x, cell.y);
+
+        // Rows wrap around from west to east, but not over the poles
+        let west = &world.terrain[y][(x + width - 1) % width];
+        let east = &world.terrain[y][(x + 1) % width];
+        let south = &world.terrain[y.saturating_sub(1)][x];
+        let north = &world.terrain[(y + 1).min(height - 1)][x];
+
+        let cell_height = Hillshade::CIRCUMFERENCE / 2.0 / height as f32;
+        // Cells get narrower towards the poles
+        let cell_width = (Hillshade::CIRCUMFERENCE / width as f32 * world.latitude(y).cos())
+            .max(cell_height / 100.0);
+        let north_rows = (y + 1).min(height - 1) - y.saturating_sub(1);
+
+        let east_gradient =
+            self.exaggeration * (east.altitude - west.altitude) / (2.0 * cell_width);
+        let north_gradient = if north_rows == 0 {
+            0.0
+        } else {
+            self.exaggeration * (north.altitude - south.altitude)
+                / (north_rows as f32 * cell_height)
+        };
+
+        let (sin_azimuth, cos_azimuth) = self.azimuth.to_radians().sin_cos();
+        let (sin_elevation, cos_elevation) = self.elevation.to_radians().sin_cos();
+        let light = Vec3::new(
+            sin_azimuth * cos_elevation,
+            cos_azimuth * cos_elevation,
+            sin_elevation,
+        );
+        let normal = Vec3::new(-east_gradient, -north_gradient, 1.0).normalize();
+        normal.dot(light).max(0.0)
+    }
+}
+
+#[must_use]
+pub(crate) fn altitude_contour_color(world: &World, altitude: f32) -> Rgb {
+    let mut color = Rgb::new(1.0, 0.6, 0.0);
+
+    let mut shade_value = 1.0;
+    let mut value = f32::max(0.0, altitude / world.max_altitude);
+
+    if altitude < 0.0 {
+        value = f32::max(0.0, 1.0 - (altitude / world.min_altitude));
+        color = Rgb::BLUE;
+    }
+
+    while shade_value > value {
+        shade_value -= 0.15;
+    }
+    shade_value = 0.5 * shade_value + 0.5;
+
+    Rgb::new(
+        color.r() * shade_value,
+        color.g() * shade_value,
+        color.b() * shade_value,
+    )
+}
+
+#[must_use]
+pub(crate) fn rainfall_color(rainfall: f32, ramp: &ColorRamp) -> Rgb {
+    let value = rainfall / World::MAX_RAINFALL;
+    ramp.sample(value).unwrap_or(if rainfall <= 0.0 {
+        Rgb::BLACK
+    } else {
+        Rgb::new(0.0, value, 0.0)
+    })
+}
+
+#[must_use]
+pub(crate) fn temperature_color(temperature: f32, ramp: &ColorRamp) -> Rgb {
+    let value = (temperature - World::MIN_TEMPERATURE) / World::TEMPERATURE_SPAN;
+    ramp.sample(value)
+        .unwrap_or(Rgb::new(value, 0.0, 1.0 - value))
+}
+
+#[must_use]
+pub(crate) fn arability_color(arability: f32, ramp: &ColorRamp) -> Rgb {
+    ramp.sample(arability).unwrap_or(if arability <= 0.0 {
+        Rgb::BLACK
+    } else {
+        Rgb::new(1.0 - arability, 0.5 + 0.5 * arability, 0.0)
+    })
+}
+
+#[must_use]
+fn resource_color(cell: &TerrainCell) -> Rgb {
+    match cell
+        .resources
+        .iter()
+        .max_by(|(_, amount_a), (_, amount_b)| amount_a.total_cmp(amount_b))
+    {
+        Some((resource_type, _)) => resource_type.stats().color,
+        None => Rgb::BLACK,
+    }
+}
+
+#[must_use]
+fn biome_color(
+    world: &World,
+    cell: &TerrainCell,
+    palette: BiomePalette,
+    shade_slopes: bool,
+) -> Rgb {
+    let slant_factor = if shade_slopes {
+        let slant = world.get_slant(cell);
+        f32::min(1.0, (4.0 + (10.0 * slant / World::ALTITUDE_SPAN)) / 5.0)
+    } else {
+        1.0
+    };
+    let altitude_factor = f32::min(
+        1.0,
+        (0.5 + (cell.altitude - World::MIN_ALTITUDE) / World::ALTITUDE_SPAN) / 1.5,
+    );
+
+    let mut red = 0.0;
+    let mut green = 0.0;
+    let mut blue = 0.0;
+
+    for (biome, presence) in cell.biome_presences.iter() {
+        let color = palette.color(*biome);
+        red += color.r() * presence;
+        green += color.g() * presence;
+        blue += color.b() * presence;
+    }
+    red *= slant_factor * altitude_factor;
+    green *= slant_factor * altitude_factor;
+    blue *= slant_factor * altitude_factor;
+    Rgb::new(red, green, blue)
+}
+
+#[must_use]
+fn climate_color(world: &World, cell: &TerrainCell, palette: BiomePalette) -> Rgb {
+    match world.koppen_class(cell) {
+        Some(koppen_class) => koppen_class.color(),
+        None => palette.color(BiomeType::Ocean),
+    }
+}
+
+pub const COASTLINE_PALETTE: [Rgb; 5] = [
+    Rgb::new(251.0 / 255.0, 230.0 / 255.0, 197.0 / 255.0),
+    Rgb::new(233.0 / 255.0, 192.0 / 255.0, 136.0 / 255.0),
+    Rgb::new(74.0 / 255.0, 39.0 / 255.0, 13.0 / 255.0),
+    Rgb::new(155.0 / 255.0, 105.0 / 255.0, 72.0 / 255.0),
+    Rgb::new(188.0 / 255.0, 136.0 / 255.0, 84.0 / 255.0),
+];
+/// Colours of the coastlines view in the order of [`COASTLINE_PALETTE`]. The
+/// classic colours go from light to dark as water, land, steep land, land
+/// near the coast and coastline, so other ramps are sampled in that order.
+#[must_use]
+pub(crate) fn coastline_palette(ramp: &ColorRamp) -> [Rgb; 5] {
+    let mut palette = COASTLINE_PALETTE;
+    for (index, value) in [(0, 0.0), (1, 0.25), (4, 0.5), (3, 0.75), (2, 1.0)] {
+        if let Some(color) = ramp.sample(value) {
+            palette[index] = color;
+        }
+    }
+    palette
+}
+
+#[must_use]
+fn coastline_color(world: &World, cell: &TerrainCell, palette: &[Rgb; 5]) -> Rgb {
+    if world.is_cell_coastline(cell) {
+        palette[2]
+    } else if world.is_cell_near_coastline(cell) {
+        palette[3]
+    } else if cell.altitude > 0.0 {
+        let slant = world.get_slant(cell);
+        let altitude_span = world.max_altitude - world.min_altitude;
+        let slant_factor = f32::min(1.0, -(20.0 * (slant / altitude_span)));
+
+        if slant_factor > 0.1 {
+            palette[4] * slant_factor + palette[1] * (1.0 - slant_factor)
+        } else {
+            palette[1]
+        }
+    } else {
+        palette[0]
+    }
+}
+
+pub(crate) const ALTITUDE_CONTOUR_COLOR: Rgb = Rgb::new(0.3, 0.2, 0.1);
+pub(crate) const RAINFALL_CONTOUR_COLOR: Rgb = Rgb::new(0.0, 0.25, 0.8);
+pub(crate) const TEMPERATURE_CONTOUR_COLOR: Rgb = Rgb::new(0.8, 0.1, 0.1);
+
+/// Whether an isoline runs along the cell, which is when a multiple of the
+/// interval lies between its value and that of its east or north neighbour.
+/// Only looking one way keeps the lines one cell wide.
+#[must_use]
+pub fn on_isoline(
+    world: &World,
+    cell: &TerrainCell,
+    interval: f32,
+    value: impl Fn(&TerrainCell) -> f32,
+) -> bool {
+    if interval <= 0.0 {
+        return false;
+    }
+    let band = (value(cell) / interval).floor();
+    let east = &world.terrain[cell.y][(cell.x + 1) % world.width as usize];
+    let north = world.terrain.get(cell.y + 1).map(|row| &row[cell.x]);
+
+    (value(east) / interval).floor() != band
+        || north.is_some_and(|north| (value(north) / interval).floor() != band)
+}
+
+/// Colour of the contour line of a visible contour overlay running along the
+/// cell, if any. Later overlays are drawn over earlier ones.
+#[must_use]
+fn contour_color(
+    world: &World,
+    cell: &TerrainCell,
+    overlays: &HashSet<WorldOverlay>,
+    contours: &ContourIntervals,
+) -> Option<Rgb> {
+    let mut color = None;
+    if overlays.contains(&WorldOverlay::AltitudeContours)
+        && on_isoline(world, cell, contours.altitude, |cell| cell.altitude)
+    {
+        color = Some(ALTITUDE_CONTOUR_COLOR);
+    }
+    if overlays.contains(&WorldOverlay::RainfallContours)
+        && on_isoline(world, cell, contours.rainfall, |cell| cell.rainfall)
+    {
+        color = Some(RAINFALL_CONTOUR_COLOR);
+    }
+    if overlays.contains(&WorldOverlay::TemperatureContours)
+        && on_isoline(world, cell, contours.temperature, |cell| cell.temperature)
+    {
+        color = Some(TEMPERATURE_CONTOUR_COLOR);
+    }
+    color
+}
+
+/// Colour of a cell on the map, showing the view with the overlays blended
+/// over it, shaded by the hillshade if it is multiplied over the view, and
+/// with contour lines on top
+#[must_use]
+pub fn cell_color(
+    world: &World,
+    cell: &TerrainCell,
+    view: WorldView,
+    overlays: &HashSet<WorldOverlay>,
+    style: &MapStyle,
+) -> Rgb {
+    let hillshade = &style.hillshade;
+    let colors = &style.colors;
+    let base_color = match view {
+        // Slopes are already shaded by the hillshade when it's multiplied
+        WorldView::Biomes => biome_color(world, cell, colors.biomes, !hillshade.multiply),
+        WorldView::Topography => altitude_contour_color(world, cell.altitude),
+        WorldView::Coastlines => {
+            coastline_color(world, cell, &coastline_palette(&colors.coastlines))
+        },
+        WorldView::Climate => climate_color(world, cell, colors.biomes),
+        WorldView::Relief => Rgb::WHITE * hillshade.shade(world, cell),
+    };
+    let mut normalizer = 1.0;
+
+    let mut red = base_color.r();
+    let mut green = base_color.g();
+    let mut blue = base_color.b();
+
+    if overlays.iter().any(|overlay| !overlay.is_contour()) {
+        let grey = (red + green + blue) / 3.0;
+        red = grey;
+        green = grey;
+        blue = grey;
+    }
+
+    if overlays.contains(&WorldOverlay::Rainfall) {
+        normalizer += 1.0;
+        let rainfall_color = rainfall_color(cell.rainfall, &colors.rainfall);
+
+        red += rainfall_color.r();
+        green += rainfall_color.g();
+        blue += rainfall_color.b();
+    }
+
+    if overlays.contains(&WorldOverlay::Temperature) {
+        normalizer += 1.0;
+        let temperature_color = temperature_color(cell.temperature, &colors.temperature);
+
+        red += temperature_color.r();
+        green += temperature_color.g();
+        blue += temperature_color.b();
+    }
+
+    if overlays.contains(&WorldOverlay::Arability) {
+        normalizer += 1.0;
+        let arability_color = arability_color(cell.arability, &colors.arability);
+
+        red += arability_color.r();
+        green += arability_color.g();
+        blue += arability_color.b();
+    }
+
+    if overlays.contains(&WorldOverlay::Resources) {
+        normalizer += 1.0;
+        let resource_color = resource_color(cell);
+
+        red += resource_color.r();
+        green += resource_color.g();
+        blue += resource_color.b();
+    }
+
+    let mut color = Rgb::new(red / normalizer, green / normalizer, blue / normalizer);
+    if hillshade.multiply && view != WorldView::Relief {
+        color = color * hillshade.shade(world, cell);
+    }
+    contour_color(world, cell, overlays, &style.contours).unwrap_or(color)
+}
+
+/// Colours of the whole map, row by row from the north edge to the south edge
+pub fn map_colors<'a>(
+    world: &'a World,
+    view: WorldView,
+    overlays: &'a HashSet<WorldOverlay>,
+    style: &'a MapStyle,
+) -> impl Iterator<Item = Rgb> + 'a {
+    world
+        .terrain
+        .iter()
+        .rev()
+        .flatten()
+        .map(move |cell| cell_color(world, cell, view, overlays, style))
+}
+
+/// Colours of the map drawn in a projection, from the colours of
+/// [`map_colors`]. Pixels outside of the projected world are `None`.
+pub fn projected_map_colors<'a>(
+    world: &World,
+    colors: &'a [Rgb],
+    projection: &'a Projection,
+) -> impl Iterator<Item = Option<Rgb>> + 'a {
+    let width = world.width as usize;
+    let height = world.height as usize;
+    projection
+        .resample(world.width, world.height)
+        .map(move |cell| cell.map(|(x, y)| colors[(height - 1 - y) * width + x]))
+}
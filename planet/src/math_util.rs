@@ -1,6 +1,6 @@
 use {
     bevy::math::Vec3A,
-    rand::{rngs::StdRng, Rng},
+    rand::Rng,
     std::{
         error::Error,
         f32::consts::{PI, TAU},
@@ -63,7 +63,7 @@ pub fn cartesian_coordinates(
 }
 
 #[must_use]
-pub fn random_point_in_sphere(rng: &mut StdRng, radius: f32) -> Vec3A {
+pub fn random_point_in_sphere<R: Rng>(rng: &mut R, radius: f32) -> Vec3A {
     // https://karthikkaranth.me/blog/generating-random-points-in-a-sphere/#better-choice-of-spherical-coordinates
 
     let u = rng.gen_range(0.0..1.0);
@@ -0,0 +1,261 @@
+//! Colours the scales and categories of the map can be drawn with, besides the
+//! classic ones. The named colour maps and the colour-blind safe biome palette
+//! can be told apart with the common colour vision deficiencies, and users can
+//! add their own palettes from a file.
+
+use {
+    crate::{color::Rgb, macros::iterable_enum, BiomeType},
+    serde::{Deserialize, Serialize},
+    std::{error::Error, fmt::Display, fs, io, path::Path},
+};
+
+iterable_enum!(ColorMap {
+    Viridis,
+    Cividis,
+    Magma,
+    Inferno,
+    Diverging,
+    Greyscale
+});
+
+impl ColorMap {
+    /// Colours at evenly spaced points along the map, as `0xRRGGBB`
+    #[must_use]
+    fn stops(self) -> &'static [u32] {
+        match self {
+            ColorMap::Viridis => &[
+                0x440154, 0x472d7b, 0x3b528b, 0x2c728e, 0x21918c, 0x28ae80, 0x5ec962, 0xaddc30,
+                0xfde725,
+            ],
+            ColorMap::Cividis => &[
+                0x00224e, 0x123570, 0x3b496c, 0x575d6d, 0x707173, 0x8a8678, 0xa59c74, 0xc3b369,
+                0xfee838,
+            ],
+            ColorMap::Magma => &[
+                0x000004, 0x1c1044, 0x4f127b, 0x812581, 0xb5367a, 0xe55064, 0xfb8761, 0xfec287,
+                0xfcfdbf,
+            ],
+            ColorMap::Inferno => &[
+                0x000004, 0x1f0c48, 0x550f6d, 0x88226a, 0xba3655, 0xe35933, 0xf98e09, 0xf9cb35,
+                0xfcffa4,
+            ],
+            // Blue to red through white, which stays readable without telling
+            // red and green apart
+            ColorMap::Diverging => &[
+                0x2166ac, 0x4393c3, 0x92c5de, 0xd1e5f0, 0xf7f7f7, 0xfddbc7, 0xf4a582, 0xd6604d,
+                0xb2182b,
+            ],
+            ColorMap::Greyscale => &[0x000000, 0xffffff],
+        }
+    }
+
+    /// Colour at `value`, from 0 at the low end of the map to 1 at its high end
+    #[must_use]
+    pub fn sample(self, value: f32) -> Rgb {
+        let stops = self.stops();
+        interpolate(stops.len(), |index| hex_color(stops[index]), value)
+    }
+}
+
+/// Colours of a continuous scale of the map, like temperature or rainfall
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum ColorRamp {
+    /// The colours the scale has always been drawn with, which differ from
+    /// scale to scale
+    #[default]
+    Classic,
+    Map(ColorMap),
+    Custom(Palette),
+}
+
+impl ColorRamp {
+    /// Colour at `value`, from 0 at the low end of the scale to 1 at its high
+    /// end. `None` for the classic colours, which each scale picks itself.
+    #[must_use]
+    pub fn sample(&self, value: f32) -> Option<Rgb> {
+        match self {
+            ColorRamp::Classic => None,
+            ColorRamp::Map(map) => Some(map.sample(value)),
+            ColorRamp::Custom(palette) => Some(palette.sample(value)),
+        }
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            ColorRamp::Classic => "Classic",
+            ColorRamp::Map(map) => map.into(),
+            ColorRamp::Custom(palette) => &palette.name,
+        }
+    }
+}
+
+/// A user-defined colour map, evenly spreading its colours along the scale
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Palette {
+    pub name:   String,
+    pub colors: Vec<Rgb>,
+}
+
+/// How palettes are written in a palette file, with colours as `#RRGGBB`
+#[derive(Deserialize)]
+struct PaletteDefinition {
+    name:   String,
+    colors: Vec<String>,
+}
+
+impl Palette {
+    pub const EXTENSION: &'static str = "ron";
+    pub const FILE_NAME: &'static str = "palettes.ron";
+
+    /// Colour at `value`, from 0 at the low end of the scale to 1 at its high
+    /// end
+    #[must_use]
+    pub fn sample(&self, value: f32) -> Rgb {
+        interpolate(self.colors.len(), |index| self.colors[index], value)
+    }
+
+    /// Reads a list of palettes, like
+    ///
+    /// ```ron
+    /// [
+    ///     (name: "Sea to summit", colors: ["#08306b", "#c7e9c0", "#ffffff"]),
+    /// ]
+    /// ```
+    pub fn from_ron(palettes: &str) -> Result<Vec<Palette>, PaletteError> {
+        let definitions: Vec<PaletteDefinition> =
+            ron::from_str(palettes).map_err(PaletteError::InvalidRon)?;
+        definitions
+            .into_iter()
+            .map(|definition| {
+                if definition.colors.len() < 2 {
+                    return Err(PaletteError::TooFewColors(definition.name));
+                }
+                let colors = definition
+                    .colors
+                    .iter()
+                    .map(|color| {
+                        parse_hex_color(color).ok_or_else(|| PaletteError::InvalidColor {
+                            palette: definition.name.clone(),
+                            color:   color.clone(),
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(Palette {
+                    name: definition.name,
+                    colors,
+                })
+            })
+            .collect()
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Vec<Palette>, PaletteError> {
+        let palettes = fs::read_to_string(path).map_err(PaletteError::FailedToRead)?;
+        Palette::from_ron(&palettes)
+    }
+}
+
+#[derive(Debug)]
+pub enum PaletteError {
+    FailedToRead(io::Error),
+    InvalidRon(ron::error::SpannedError),
+    InvalidColor { palette: String, color: String },
+    TooFewColors(String),
+}
+impl Error for PaletteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PaletteError::FailedToRead(error) => Some(error),
+            PaletteError::InvalidRon(error) => Some(error),
+            PaletteError::InvalidColor { .. } => None,
+            PaletteError::TooFewColors(_) => None,
+        }
+    }
+
+    fn description(&self) -> &str {
+        "description() is deprecated; use Display"
+    }
+
+    fn cause(&self) -> Option<&dyn Error> {
+        self.source()
+    }
+}
+impl Display for PaletteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PaletteError::FailedToRead(err) => {
+                f.write_fmt(format_args!("Failed to read palettes - {err}"))
+            },
+            PaletteError::InvalidRon(err) => {
+                f.write_fmt(format_args!("Palette file is not valid - {err}"))
+            },
+            PaletteError::InvalidColor { palette, color } => f.write_fmt(format_args!(
+                "Palette '{palette}' has invalid colour '{color}', expected #RRGGBB"
+            )),
+            PaletteError::TooFewColors(palette) => f.write_fmt(format_args!(
+                "Palette '{palette}' needs at least two colours"
+            )),
+        }
+    }
+}
+
+iterable_enum!(BiomePalette {
+    Classic,
+    ColorBlindSafe
+});
+
+impl BiomePalette {
+    #[must_use]
+    pub fn color(self, biome: BiomeType) -> Rgb {
+        match self {
+            BiomePalette::Classic => biome.stats().color,
+            BiomePalette::ColorBlindSafe => hex_color(color_blind_safe_biome_color(biome)),
+        }
+    }
+}
+
+/// Colours from the Okabe-Ito and Paul Tol palettes. Neighbouring biomes
+/// differ in lightness as well as hue, so they stay apart without telling red
+/// and green apart.
+fn color_blind_safe_biome_color(biome: BiomeType) -> u32 {
+    match biome {
+        BiomeType::IceCap => 0xf0f0f0,
+        BiomeType::Ocean => 0x004488,
+        BiomeType::Grassland => 0x999933,
+        BiomeType::Forest => 0x117733,
+        BiomeType::Taiga => 0x44aa99,
+        BiomeType::Tundra => 0xbbbbbb,
+        BiomeType::Desert => 0xf0e442,
+        BiomeType::Rainforest => 0x225522,
+        BiomeType::Alpine => 0x99ddff,
+        BiomeType::Mountain => 0x666666,
+        BiomeType::Savanna => 0xe69f00,
+        BiomeType::Steppe => 0xcc6677,
+        BiomeType::Wetland => 0x332288,
+        BiomeType::Mangrove => 0xaa4499,
+        BiomeType::ShallowSea => 0x56b4e9,
+        BiomeType::DeepOcean => 0x002244,
+        BiomeType::CoralReef => 0xee3377,
+    }
+}
+
+fn hex_color(color: u32) -> Rgb {
+    let [_, red, green, blue] = color.to_be_bytes();
+    Rgb::rgb_u8(red, green, blue)
+}
+
+fn parse_hex_color(color: &str) -> Option<Rgb> {
+    let digits = color.strip_prefix('#')?;
+    if digits.len() != 6 || !digits.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(digits, 16).ok().map(hex_color)
+}
+
+/// Linear interpolation between `count` colours spread evenly from 0 to 1
+fn interpolate(count: usize, stop: impl Fn(usize) -> Rgb, value: f32) -> Rgb {
+    let position = value.clamp(0.0, 1.0) * (count - 1) as f32;
+    let index = (position.floor() as usize).min(count - 2);
+    let fraction = position - index as f32;
+    stop(index) * (1.0 - fraction) + stop(index + 1) * fraction
+}
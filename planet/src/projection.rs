@@ -0,0 +1,182 @@
+//! Map projections, used to resample the terrain grid into the map image.
+//!
+//! The terrain is an equirectangular grid, so every projection is implemented
+//! as its inverse: from a position in the map image to a longitude and
+//! latitude, and from there to a cell. That way every pixel of the image gets
+//! exactly one cell, and the same inverse tells which cell is under the
+//! cursor.
+
+use {
+    crate::macros::iterable_enum,
+    serde::{Deserialize, Serialize},
+    std::f32::consts::{FRAC_PI_2, PI},
+};
+
+iterable_enum!(MapProjection {
+    Equirectangular,
+    Mollweide,
+    Robinson,
+    Sinusoidal,
+    Orthographic,
+});
+
+/// Robinson's table of the parallels' length and distance from the equator,
+/// every 5 degrees from the equator to the pole
+const ROBINSON_TABLE: [(f32, f32); 19] = [
+    (1.0000, 0.0000),
+    (0.9986, 0.0620),
+    (0.9954, 0.1240),
+    (0.9900, 0.1860),
+    (0.9822, 0.2480),
+    (0.9730, 0.3100),
+    (0.9600, 0.3720),
+    (0.9427, 0.4340),
+    (0.9216, 0.4958),
+    (0.8962, 0.5571),
+    (0.8679, 0.6176),
+    (0.8350, 0.6769),
+    (0.7986, 0.7346),
+    (0.7597, 0.7903),
+    (0.7186, 0.8435),
+    (0.6732, 0.8936),
+    (0.6213, 0.9394),
+    (0.5722, 0.9761),
+    (0.5322, 1.0000),
+];
+const ROBINSON_STEP: f32 = 5.0 * PI / 180.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Projection {
+    pub kind:             MapProjection,
+    /// Longitude in the middle of the map, in degrees
+    pub center_longitude: f32,
+    /// Latitude in the middle of the globe, in degrees. Only the orthographic
+    /// projection can be tilted.
+    pub center_latitude:  f32,
+}
+impl Default for Projection {
+    fn default() -> Self {
+        Projection {
+            kind:             MapProjection::Equirectangular,
+            center_longitude: 0.0,
+            center_latitude:  0.0,
+        }
+    }
+}
+
+impl Projection {
+    /// Longitude and latitude in radians at a position of the map, where
+    /// `x` and `y` go from -1 at the west and south edges to 1 at the east
+    /// and north edges. `None` outside of the projected world.
+    #[must_use]
+    pub fn inverse(&self, x: f32, y: f32) -> Option<(f32, f32)> {
+        if !(-1.0..=1.0).contains(&x) || !(-1.0..=1.0).contains(&y) {
+            return None;
+        }
+
+        let (longitude, latitude) = match self.kind {
+            MapProjection::Equirectangular => (x * PI, y * FRAC_PI_2),
+            MapProjection::Sinusoidal => {
+                let latitude = y * FRAC_PI_2;
+                (x * PI / latitude.cos(), latitude)
+            },
+            MapProjection::Mollweide => {
+                let theta = y.asin();
+                let latitude = ((2.0 * theta + (2.0 * theta).sin()) / PI)
+                    .clamp(-1.0, 1.0)
+                    .asin();
+                (x * PI / theta.cos(), latitude)
+            },
+            MapProjection::Robinson => {
+                let (length, latitude) = robinson_inverse(y.abs());
+                (x * PI / length, latitude.copysign(y))
+            },
+            MapProjection::Orthographic => {
+                return self.orthographic_inverse(x, y);
+            },
+        };
+        if !longitude.is_finite() || longitude.abs() > PI {
+            return None;
+        }
+        Some((
+            wrap_longitude(longitude + self.center_longitude.to_radians()),
+            latitude,
+        ))
+    }
+
+    /// Cell at a position of a map image as large as the world, where `x`
+    /// goes from 0 at the west edge to the world's width at the east edge,
+    /// and `y` from 0 at the north edge to the world's height at the south
+    /// edge. Returns the cell's `x` and `y`, or `None` outside of the
+    /// projected world.
+    #[must_use]
+    pub fn cell_at(&self, width: u32, height: u32, x: f32, y: f32) -> Option<(usize, usize)> {
+        let (longitude, latitude) =
+            self.inverse(x / width as f32 * 2.0 - 1.0, 1.0 - y / height as f32 * 2.0)?;
+
+        let cell_x = ((longitude + PI) / (2.0 * PI) * width as f32).floor() as i64;
+        let cell_y = ((latitude + FRAC_PI_2) / PI * height as f32).floor() as i64;
+        Some((
+            cell_x.rem_euclid(i64::from(width)) as usize,
+            cell_y.clamp(0, i64::from(height) - 1) as usize,
+        ))
+    }
+
+    /// Cells under the centre of each pixel of a map image as large as the
+    /// world, row by row from the north edge to the south edge
+    pub fn resample(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> impl Iterator<Item = Option<(usize, usize)>> + '_ {
+        (0..height).flat_map(move |y| {
+            (0..width).map(move |x| self.cell_at(width, height, x as f32 + 0.5, y as f32 + 0.5))
+        })
+    }
+
+    /// The globe fills the height of the map, which is twice as wide as high
+    fn orthographic_inverse(&self, x: f32, y: f32) -> Option<(f32, f32)> {
+        let x = x * 2.0;
+        let rho = x.hypot(y);
+        if rho > 1.0 {
+            return None;
+        }
+        let center_longitude = self.center_longitude.to_radians();
+        if rho == 0.0 {
+            return Some((
+                wrap_longitude(center_longitude),
+                self.center_latitude.to_radians(),
+            ));
+        }
+
+        let (sin_center, cos_center) = self.center_latitude.to_radians().sin_cos();
+        let (sin_c, cos_c) = rho.asin().sin_cos();
+        let latitude = (cos_c * sin_center + y * sin_c * cos_center / rho)
+            .clamp(-1.0, 1.0)
+            .asin();
+        let longitude = center_longitude
+            + f32::atan2(x * sin_c, rho * cos_c * cos_center - y * sin_c * sin_center);
+        Some((wrap_longitude(longitude), latitude))
+    }
+}
+
+/// Length of the parallel and its latitude in radians, at a distance from
+/// the equator between 0 and 1
+fn robinson_inverse(distance: f32) -> (f32, f32) {
+    let index = ROBINSON_TABLE
+        .windows(2)
+        .position(|pair| distance <= pair[1].1)
+        .unwrap_or(ROBINSON_TABLE.len() - 2);
+    let (length_a, distance_a) = ROBINSON_TABLE[index];
+    let (length_b, distance_b) = ROBINSON_TABLE[index + 1];
+    let t = ((distance - distance_a) / (distance_b - distance_a)).clamp(0.0, 1.0);
+
+    (
+        length_a + (length_b - length_a) * t,
+        (index as f32 + t) * ROBINSON_STEP,
+    )
+}
+
+fn wrap_longitude(longitude: f32) -> f32 {
+    (longitude + PI).rem_euclid(2.0 * PI) - PI
+}
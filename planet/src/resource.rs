@@ -0,0 +1,184 @@
+use crate::{color::Rgb, macros::iterable_enum, BiomeType, World};
+
+#[derive(Debug, Clone)]
+pub struct ResourceStats {
+    pub name:         &'static str,
+    pub color:        Rgb,
+    pub min_altitude: f32,
+    pub max_altitude: f32,
+    pub min_slope:    f32,
+    pub max_slope:    f32,
+    /// Biomes the resource can be found in. If empty, any biome will do.
+    pub biomes:       &'static [BiomeType],
+    /// Chance of a fully suitable cell holding a deposit, at a resource
+    /// scarcity of 1
+    pub frequency:    f32,
+}
+
+iterable_enum!(ResourceType {
+    Stone,
+    Clay,
+    Copper,
+    Tin,
+    Iron,
+    Gold,
+    Coal,
+    Salt,
+    Timber,
+    Fish,
+});
+
+impl ResourceType {
+    #[must_use]
+    pub fn stats(self) -> &'static ResourceStats {
+        &RESOURCE_STATS[self as usize]
+    }
+}
+
+impl From<ResourceType> for &'static ResourceStats {
+    fn from(resource_type: ResourceType) -> &'static ResourceStats {
+        resource_type.stats()
+    }
+}
+
+impl From<&ResourceType> for &'static ResourceStats {
+    fn from(resource_type: &ResourceType) -> &'static ResourceStats {
+        resource_type.stats()
+    }
+}
+
+// Must be in the same order as the ResourceType variants
+static RESOURCE_STATS: [ResourceStats; ResourceType::ITEM_COUNT] = [
+    // ResourceType::Stone
+    ResourceStats {
+        name:         "Stone",
+        color:        Rgb::new(150.0 / 255.0, 150.0 / 255.0, 150.0 / 255.0),
+        min_altitude: 0.0,
+        max_altitude: World::MAX_ALTITUDE,
+        min_slope:    50.0,
+        max_slope:    World::MAX_SLOPE,
+        biomes:       &[],
+        frequency:    0.3,
+    },
+    // ResourceType::Clay
+    ResourceStats {
+        name:         "Clay",
+        color:        Rgb::new(190.0 / 255.0, 110.0 / 255.0, 70.0 / 255.0),
+        min_altitude: 0.0,
+        max_altitude: 1000.0,
+        min_slope:    World::MIN_SLOPE,
+        max_slope:    50.0,
+        biomes:       &[
+            BiomeType::Wetland,
+            BiomeType::Grassland,
+            BiomeType::Forest,
+            BiomeType::Rainforest,
+            BiomeType::Savanna,
+            BiomeType::Mangrove,
+        ],
+        frequency:    0.1,
+    },
+    // ResourceType::Copper
+    ResourceStats {
+        name:         "Copper",
+        color:        Rgb::new(200.0 / 255.0, 120.0 / 255.0, 50.0 / 255.0),
+        min_altitude: 500.0,
+        max_altitude: World::MAX_ALTITUDE,
+        min_slope:    100.0,
+        max_slope:    World::MAX_SLOPE,
+        biomes:       &[],
+        frequency:    0.05,
+    },
+    // ResourceType::Tin
+    ResourceStats {
+        name:         "Tin",
+        color:        Rgb::new(210.0 / 255.0, 210.0 / 255.0, 230.0 / 255.0),
+        min_altitude: 500.0,
+        max_altitude: World::MAX_ALTITUDE,
+        min_slope:    100.0,
+        max_slope:    World::MAX_SLOPE,
+        biomes:       &[],
+        frequency:    0.02,
+    },
+    // ResourceType::Iron
+    ResourceStats {
+        name:         "Iron",
+        color:        Rgb::new(140.0 / 255.0, 60.0 / 255.0, 50.0 / 255.0),
+        min_altitude: 0.0,
+        max_altitude: World::MAX_ALTITUDE,
+        min_slope:    30.0,
+        max_slope:    World::MAX_SLOPE,
+        biomes:       &[],
+        frequency:    0.06,
+    },
+    // ResourceType::Gold
+    ResourceStats {
+        name:         "Gold",
+        color:        Rgb::new(1.0, 210.0 / 255.0, 0.0),
+        min_altitude: 1000.0,
+        max_altitude: World::MAX_ALTITUDE,
+        min_slope:    150.0,
+        max_slope:    World::MAX_SLOPE,
+        biomes:       &[],
+        frequency:    0.01,
+    },
+    // ResourceType::Coal
+    ResourceStats {
+        name:         "Coal",
+        color:        Rgb::new(30.0 / 255.0, 30.0 / 255.0, 30.0 / 255.0),
+        min_altitude: 0.0,
+        max_altitude: 3000.0,
+        min_slope:    World::MIN_SLOPE,
+        max_slope:    200.0,
+        biomes:       &[
+            BiomeType::Forest,
+            BiomeType::Rainforest,
+            BiomeType::Taiga,
+            BiomeType::Wetland,
+        ],
+        frequency:    0.04,
+    },
+    // ResourceType::Salt
+    ResourceStats {
+        name:         "Salt",
+        color:        Rgb::new(1.0, 1.0, 1.0),
+        min_altitude: 0.0,
+        max_altitude: 2000.0,
+        min_slope:    World::MIN_SLOPE,
+        max_slope:    World::MAX_SLOPE,
+        biomes:       &[BiomeType::Desert, BiomeType::Steppe],
+        frequency:    0.15,
+    },
+    // ResourceType::Timber
+    ResourceStats {
+        name:         "Timber",
+        color:        Rgb::new(30.0 / 255.0, 110.0 / 255.0, 30.0 / 255.0),
+        min_altitude: 0.0,
+        max_altitude: World::MAX_ALTITUDE,
+        min_slope:    World::MIN_SLOPE,
+        max_slope:    World::MAX_SLOPE,
+        biomes:       &[
+            BiomeType::Forest,
+            BiomeType::Rainforest,
+            BiomeType::Taiga,
+            BiomeType::Mangrove,
+        ],
+        frequency:    0.6,
+    },
+    // ResourceType::Fish
+    ResourceStats {
+        name:         "Fish",
+        color:        Rgb::new(80.0 / 255.0, 160.0 / 255.0, 1.0),
+        min_altitude: World::MIN_ALTITUDE,
+        max_altitude: World::MAX_ALTITUDE,
+        min_slope:    World::MIN_SLOPE,
+        max_slope:    World::MAX_SLOPE,
+        biomes:       &[
+            BiomeType::ShallowSea,
+            BiomeType::CoralReef,
+            BiomeType::Wetland,
+            BiomeType::Mangrove,
+        ],
+        frequency:    0.4,
+    },
+];
@@ -0,0 +1,82 @@
+//! Writes files so that they are either fully replaced or left untouched,
+//! never half-written.
+
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Temporary file next to `path`, on the same file system so it can be
+/// renamed over it
+fn temporary_path(path: &Path) -> io::Result<PathBuf> {
+    let Some(file_name) = path.file_name() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a file path", path.display()),
+        ));
+    };
+    let mut temporary_name = std::ffi::OsString::from(".");
+    temporary_name.push(file_name);
+    temporary_name.push(format!(".{}.tmp", std::process::id()));
+    Ok(path.with_file_name(temporary_name))
+}
+
+/// Writes the parts one after another to a temporary file, flushes it to disk
+/// and renames it over `path`. If anything fails, the temporary file is
+/// removed and `path` keeps its previous contents.
+pub fn write_atomically<P: AsRef<Path>>(path: P, parts: &[&[u8]]) -> io::Result<()> {
+    write_atomically_with(path, |file| {
+        for part in parts {
+            file.write_all(part)?;
+        }
+        Ok(())
+    })
+}
+
+/// Like [`write_atomically`], with `write` writing the contents to the
+/// temporary file
+pub fn write_atomically_with<P, T, E, F>(path: P, write: F) -> Result<T, E>
+where
+    P: AsRef<Path>,
+    E: From<io::Error>,
+    F: FnOnce(&mut File) -> Result<T, E>,
+{
+    let path = path.as_ref();
+    let temporary_path = temporary_path(path)?;
+
+    let written = File::create(&temporary_path)
+        .map_err(E::from)
+        .and_then(|mut file| {
+            let result = write(&mut file)?;
+            file.sync_all()?;
+            Ok(result)
+        })
+        .and_then(|result| {
+            fs::rename(&temporary_path, path)?;
+            Ok(result)
+        });
+    let result = match written {
+        Ok(result) => result,
+        Err(err) => {
+            _ = fs::remove_file(&temporary_path);
+            return Err(err);
+        },
+    };
+
+    // Make the rename itself durable. Not every platform can open directories,
+    // and the file is already complete either way.
+    #[cfg(unix)]
+    if let Some(directory) = path.parent() {
+        let directory = if directory.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            directory
+        };
+        if let Ok(directory) = File::open(directory) {
+            _ = directory.sync_all();
+        }
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,173 @@
+//! Rotating autosaves in a directory of their own, and a marker file to notice
+//! when the last session didn't exit cleanly.
+//!
+//! The newest autosave is always `autosave-0.rsplnt`. Each new autosave moves
+//! the existing ones up by one slot, and the one in the last slot is dropped.
+
+use {
+    crate::{
+        saving::atomic,
+        world_manager::{SaveError, SaveSizeReport, WorldManager},
+    },
+    bevy::log::warn,
+    serde::{Deserialize, Serialize},
+    std::{
+        fs,
+        io,
+        path::{Path, PathBuf},
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutosaveSettings {
+    pub enabled:          bool,
+    /// Time between autosaves, in minutes
+    pub interval_minutes: u32,
+    /// How many autosaves are kept
+    pub slots:            u8,
+}
+impl Default for AutosaveSettings {
+    fn default() -> Self {
+        AutosaveSettings {
+            enabled:          true,
+            interval_minutes: 5,
+            slots:            3,
+        }
+    }
+}
+impl AutosaveSettings {
+    pub const FILE_NAME: &'static str = "autosave.ron";
+    pub const MAX_SLOTS: u8 = 10;
+}
+
+#[derive(Debug, Clone)]
+pub struct Autosaves {
+    directory: PathBuf,
+}
+
+impl Autosaves {
+    /// Newest autosave of a session that didn't exit cleanly, see
+    /// [`Autosaves::set_aside_latest`]
+    pub const RECOVERED: &'static str = "recovered.rsplnt";
+    /// Exists while a session is running, see [`Autosaves::begin_session`]
+    pub const SESSION_MARKER: &'static str = "session.lock";
+
+    #[must_use]
+    pub fn new<P: Into<PathBuf>>(directory: P) -> Autosaves {
+        Autosaves {
+            directory: directory.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    #[must_use]
+    pub fn slot_path(&self, slot: u8) -> PathBuf {
+        self.directory.join(format!("autosave-{slot}.rsplnt"))
+    }
+
+    /// Existing autosaves, newest first
+    #[must_use]
+    pub fn list(&self) -> Vec<PathBuf> {
+        (0..AutosaveSettings::MAX_SLOTS)
+            .map(|slot| self.slot_path(slot))
+            .filter(|path| path.is_file())
+            .collect()
+    }
+
+    #[must_use]
+    pub fn latest(&self) -> Option<PathBuf> {
+        self.list().into_iter().next()
+    }
+
+    /// Reads the settings stored next to the autosaves, falling back to the
+    /// defaults if there are none or they can't be read
+    #[must_use]
+    pub fn load_settings(&self) -> AutosaveSettings {
+        let path = self.directory.join(AutosaveSettings::FILE_NAME);
+        let Ok(settings) = fs::read_to_string(&path) else {
+            return AutosaveSettings::default();
+        };
+        match ron::from_str(&settings) {
+            Ok(settings) => settings,
+            Err(err) => {
+                warn!("Ignoring invalid autosave settings in {}: {err}", path.display());
+                AutosaveSettings::default()
+            },
+        }
+    }
+
+    pub fn save_settings(&self, settings: &AutosaveSettings) -> io::Result<()> {
+        let serialized = ron::ser::to_string_pretty(settings, ron::ser::PrettyConfig::default())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        fs::create_dir_all(&self.directory)?;
+        atomic::write_atomically(
+            self.directory.join(AutosaveSettings::FILE_NAME),
+            &[serialized.as_bytes()],
+        )
+    }
+
+    /// Saves the current world as the newest autosave, keeping at most `slots`
+    /// autosaves. The older autosaves are only moved once the new one is
+    /// written, so a failed autosave loses nothing.
+    pub fn write(
+        &self,
+        world_manager: &WorldManager,
+        slots: u8,
+    ) -> Result<SaveSizeReport, SaveError> {
+        let slots = slots.clamp(1, AutosaveSettings::MAX_SLOTS);
+        fs::create_dir_all(&self.directory).map_err(SaveError::FailedToWrite)?;
+
+        let incoming = self.directory.join("autosave-incoming.rsplnt");
+        let report = world_manager.save_world(&incoming)?;
+
+        for slot in (slots - 1..AutosaveSettings::MAX_SLOTS).rev() {
+            remove_if_exists(&self.slot_path(slot)).map_err(SaveError::FailedToWrite)?;
+        }
+        for slot in (0..slots - 1).rev() {
+            let path = self.slot_path(slot);
+            if path.is_file() {
+                fs::rename(&path, self.slot_path(slot + 1)).map_err(SaveError::FailedToWrite)?;
+            }
+        }
+        fs::rename(&incoming, self.slot_path(0)).map_err(SaveError::FailedToWrite)?;
+
+        Ok(report)
+    }
+
+    /// Marks a session as running. Returns whether the previous session was
+    /// still marked as running, meaning it crashed or was killed.
+    pub fn begin_session(&self) -> io::Result<bool> {
+        fs::create_dir_all(&self.directory)?;
+        let marker = self.directory.join(Autosaves::SESSION_MARKER);
+        let unclean = marker.exists();
+        fs::write(marker, std::process::id().to_string())?;
+        Ok(unclean)
+    }
+
+    /// Moves the newest autosave out of the rotation, so that it isn't
+    /// replaced before the player decided whether to restore it
+    pub fn set_aside_latest(&self) -> io::Result<Option<PathBuf>> {
+        let Some(latest) = self.latest() else {
+            return Ok(None);
+        };
+        let set_aside = self.directory.join(Autosaves::RECOVERED);
+        fs::rename(latest, &set_aside)?;
+        Ok(Some(set_aside))
+    }
+
+    /// Marks the session as cleanly ended
+    pub fn end_session(&self) -> io::Result<()> {
+        remove_if_exists(&self.directory.join(Autosaves::SESSION_MARKER))
+    }
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,94 @@
+use {
+    crate::world_manager::LoadError,
+    std::io::{self, Read, Write},
+};
+
+/// First version ending with a CRC32 of everything before it
+pub const CHECKSUM_SAVE_VERSION: u16 = 5;
+/// Length of the checksum at the end of a save
+pub const CHECKSUM_LENGTH: usize = 4;
+
+#[must_use]
+pub fn checksum(parts: &[&[u8]]) -> [u8; CHECKSUM_LENGTH] {
+    let mut hasher = crc32fast::Hasher::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().to_le_bytes()
+}
+
+/// Checks the checksum at the end of a save, returning the bytes before it
+pub fn verify_checksum(save: &[u8]) -> Result<&[u8], LoadError> {
+    let Some(content_length) = save.len().checked_sub(CHECKSUM_LENGTH) else {
+        return Err(LoadError::Corrupted);
+    };
+    let (content, stored) = save.split_at(content_length);
+    if checksum(&[content]) != stored {
+        return Err(LoadError::Corrupted);
+    }
+    Ok(content)
+}
+
+/// Checks the checksum at the end of a save without holding it in memory.
+/// `length` is the length of the whole save.
+pub fn verify_checksum_streaming<R: Read>(mut save: R, length: u64) -> Result<(), LoadError> {
+    let Some(content_length) = length.checked_sub(CHECKSUM_LENGTH as u64) else {
+        return Err(LoadError::Corrupted);
+    };
+    let mut hasher = crc32fast::Hasher::new();
+    let mut content = (&mut save).take(content_length);
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        match content.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => hasher.update(&buffer[..read]),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {},
+            Err(err) => return Err(LoadError::MissingSave(err)),
+        }
+    }
+
+    let mut stored = [0; CHECKSUM_LENGTH];
+    match save.read_exact(&mut stored) {
+        Ok(()) if stored == hasher.finalize().to_le_bytes() => Ok(()),
+        Ok(()) => Err(LoadError::Corrupted),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Err(LoadError::Corrupted),
+        Err(err) => Err(LoadError::MissingSave(err)),
+    }
+}
+
+/// Checksums everything written through it, see [`ChecksumWriter::finish`]
+pub(crate) struct ChecksumWriter<W: Write> {
+    writer:  W,
+    hasher:  crc32fast::Hasher,
+    written: usize,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    pub(crate) fn new(writer: W) -> ChecksumWriter<W> {
+        ChecksumWriter {
+            writer,
+            hasher: crc32fast::Hasher::new(),
+            written: 0,
+        }
+    }
+
+    /// Appends the checksum, returning the writer and the number of bytes
+    /// written including the checksum
+    pub(crate) fn finish(mut self) -> io::Result<(W, usize)> {
+        self.writer.write_all(&self.hasher.finalize().to_le_bytes())?;
+        Ok((self.writer, self.written + CHECKSUM_LENGTH))
+    }
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.written += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
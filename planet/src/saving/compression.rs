@@ -0,0 +1,156 @@
+use {
+    crate::{saving::SaveCompression, world_manager::LoadError},
+    miniz_oxide::{
+        deflate::core::{
+            compress_to_output,
+            create_comp_flags_from_zip_params,
+            CompressorOxide,
+            TDEFLFlush,
+            TDEFLStatus,
+        },
+        inflate::stream::{inflate, InflateState},
+        DataFormat,
+        MZError,
+        MZFlush,
+        MZStatus,
+    },
+    std::{
+        borrow::Cow,
+        io::{self, Read, Write},
+    },
+};
+
+/// Decompresses the body of a save written before version 6, see
+/// [`crate::saving::stream`]
+pub fn decompress(bytes: &[u8], compression: SaveCompression) -> Result<Cow<'_, [u8]>, LoadError> {
+    match compression {
+        SaveCompression::None => Ok(Cow::Borrowed(bytes)),
+        SaveCompression::Deflate => match miniz_oxide::inflate::decompress_to_vec(bytes) {
+            Ok(decompressed) => Ok(Cow::Owned(decompressed)),
+            Err(_) => Err(LoadError::FailedToDecompress),
+        },
+    }
+}
+
+/// Deflates everything written to it into another writer, a buffer's worth at
+/// a time. [`DeflateWriter::finish`] must be called to end the stream.
+pub(crate) struct DeflateWriter<W: Write> {
+    compressor: Box<CompressorOxide>,
+    writer:     W,
+}
+
+impl<W: Write> DeflateWriter<W> {
+    pub(crate) fn new(writer: W, level: u8) -> DeflateWriter<W> {
+        let flags = create_comp_flags_from_zip_params(level.into(), 0, 0);
+        DeflateWriter {
+            compressor: Box::new(CompressorOxide::new(flags)),
+            writer,
+        }
+    }
+
+    fn compress(&mut self, input: &[u8], flush: TDEFLFlush) -> io::Result<usize> {
+        let mut write_error = None;
+        let writer = &mut self.writer;
+        let (status, consumed) = compress_to_output(&mut self.compressor, input, flush, |output| {
+            match writer.write_all(output) {
+                Ok(()) => true,
+                Err(err) => {
+                    write_error = Some(err);
+                    false
+                },
+            }
+        });
+        if let Some(err) = write_error {
+            return Err(err);
+        }
+        match status {
+            TDEFLStatus::BadParam | TDEFLStatus::PutBufFailed => {
+                Err(io::Error::other("Failed to compress save"))
+            },
+            TDEFLStatus::Okay | TDEFLStatus::Done => Ok(consumed),
+        }
+    }
+
+    /// Ends the stream and returns the writer it was written to
+    pub(crate) fn finish(mut self) -> io::Result<W> {
+        _ = self.compress(&[], TDEFLFlush::Finish)?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> Write for DeflateWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.compress(buf, TDEFLFlush::None)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Inflates a stream read from another reader, a buffer's worth at a time.
+/// Stops at the end of the deflate stream, even if the reader goes on.
+pub(crate) struct InflateReader<R: Read> {
+    state:    Box<InflateState>,
+    reader:   R,
+    input:    Box<[u8]>,
+    position: usize,
+    length:   usize,
+    finished: bool,
+}
+
+impl<R: Read> InflateReader<R> {
+    const BUFFER_SIZE: usize = 32 * 1024;
+
+    pub(crate) fn new(reader: R) -> InflateReader<R> {
+        InflateReader {
+            state: InflateState::new_boxed(DataFormat::Raw),
+            reader,
+            input: vec![0; InflateReader::<R>::BUFFER_SIZE].into_boxed_slice(),
+            position: 0,
+            length: 0,
+            finished: false,
+        }
+    }
+}
+
+impl<R: Read> Read for InflateReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.finished || buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            if self.position == self.length {
+                self.length = self.reader.read(&mut self.input)?;
+                self.position = 0;
+            }
+            let end_of_input = self.length == 0;
+
+            let result = inflate(
+                &mut self.state,
+                &self.input[self.position..self.length],
+                buf,
+                if end_of_input {
+                    MZFlush::Finish
+                } else {
+                    MZFlush::None
+                },
+            );
+            self.position += result.bytes_consumed;
+            match result.status {
+                Ok(MZStatus::StreamEnd) => {
+                    self.finished = true;
+                    return Ok(result.bytes_written);
+                },
+                Ok(_) if result.bytes_written > 0 => return Ok(result.bytes_written),
+                Ok(_) | Err(MZError::Buf) if !end_of_input => {},
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Failed to decompress save",
+                    ))
+                },
+            }
+        }
+    }
+}
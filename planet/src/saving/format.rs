@@ -0,0 +1,49 @@
+use {
+    crate::{macros::iterable_enum, saving::WorldRecipe},
+    std::path::Path,
+};
+
+iterable_enum!(SaveFormat {
+    Binary,
+    Recipe,
+    Json,
+    Csv
+});
+
+impl SaveFormat {
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            SaveFormat::Binary => "rsplnt",
+            SaveFormat::Recipe => WorldRecipe::EXTENSION,
+            SaveFormat::Json => "json",
+            SaveFormat::Csv => "csv",
+        }
+    }
+
+    /// The CSV table is only meant for analysis, there's not enough in it to
+    /// recreate the world
+    #[must_use]
+    pub fn can_load(self) -> bool {
+        self != SaveFormat::Csv
+    }
+
+    /// Picks the format by the path's extension, falling back to
+    /// [`SaveFormat::Binary`]
+    #[must_use]
+    pub fn from_path<P: AsRef<Path>>(path: P) -> SaveFormat {
+        let extension = path.as_ref().extension();
+        SaveFormat::iterator()
+            .copied()
+            .find(|format| {
+                extension.is_some_and(|extension| extension.eq_ignore_ascii_case(format.extension()))
+            })
+            .unwrap_or(SaveFormat::Binary)
+    }
+
+    /// File dialog filter for the format, like `*.rsplnt`
+    #[must_use]
+    pub fn filter(self) -> String {
+        format!("*.{}", self.extension())
+    }
+}
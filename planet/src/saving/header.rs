@@ -0,0 +1,100 @@
+use crate::{
+    saving::{SaveCompression, SaveOptions},
+    world_manager::LoadError,
+};
+
+/// Marks a file as a world save. Saves written before the header was
+/// introduced start directly with the serialized world instead.
+pub const SAVE_MAGIC: [u8; 6] = *b"RSPLNT";
+/// Version of the save format written by this build. Must be bumped, and a
+/// migration added, whenever the records written by [`super::stream`]
+/// change.
+pub const CURRENT_SAVE_VERSION: u16 = 6;
+/// Version assigned to saves without a header
+pub const LEGACY_SAVE_VERSION: u16 = 0;
+/// First version whose header records compression and quantization
+const OPTIONS_SAVE_VERSION: u16 = 2;
+
+const QUANTIZED_FLAG: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveHeader {
+    pub version: u16,
+    pub options: SaveOptions,
+}
+
+impl SaveHeader {
+    /// Length of the header written by this build
+    pub const LENGTH: usize = SAVE_MAGIC.len() + 4;
+
+    #[must_use]
+    pub fn new(options: SaveOptions) -> SaveHeader {
+        SaveHeader {
+            version: CURRENT_SAVE_VERSION,
+            options,
+        }
+    }
+
+    /// Length of this header in the file, which depends on its version
+    #[must_use]
+    pub fn length(&self) -> usize {
+        if self.version < OPTIONS_SAVE_VERSION {
+            SAVE_MAGIC.len() + 2
+        } else {
+            SaveHeader::LENGTH
+        }
+    }
+
+    #[must_use]
+    pub fn to_bytes(self) -> [u8; SaveHeader::LENGTH] {
+        let mut bytes = [0; SaveHeader::LENGTH];
+        bytes[..SAVE_MAGIC.len()].copy_from_slice(&SAVE_MAGIC);
+        bytes[SAVE_MAGIC.len()..SAVE_MAGIC.len() + 2].copy_from_slice(&self.version.to_le_bytes());
+        bytes[SAVE_MAGIC.len() + 2] = self.options.compression as u8;
+        bytes[SAVE_MAGIC.len() + 3] = if self.options.quantize {
+            QUANTIZED_FLAG
+        } else {
+            0
+        };
+        bytes
+    }
+
+    /// Reads the header at the start of a save. Returns `Ok(None)` if the save
+    /// doesn't start with [`SAVE_MAGIC`], which means it predates the header.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Option<SaveHeader>, LoadError> {
+        if bytes.len() < SAVE_MAGIC.len() + 2 || !bytes.starts_with(&SAVE_MAGIC) {
+            return Ok(None);
+        }
+        let version = u16::from_le_bytes([bytes[SAVE_MAGIC.len()], bytes[SAVE_MAGIC.len() + 1]]);
+        if version > CURRENT_SAVE_VERSION {
+            // Newer headers may be laid out differently
+            return Err(LoadError::UnsupportedVersion(version));
+        }
+        if version < OPTIONS_SAVE_VERSION {
+            return Ok(Some(SaveHeader {
+                version,
+                options: SaveOptions {
+                    compression: SaveCompression::None,
+                    quantize:    false,
+                },
+            }));
+        }
+        if bytes.len() < SaveHeader::LENGTH {
+            return Err(LoadError::InvalidSave(
+                postcard::Error::DeserializeUnexpectedEnd,
+            ));
+        }
+
+        let compression = bytes[SAVE_MAGIC.len() + 2];
+        let Some(&compression) = SaveCompression::ITEMS.get(usize::from(compression)) else {
+            return Err(LoadError::UnsupportedCompression(compression));
+        };
+        Ok(Some(SaveHeader {
+            version,
+            options: SaveOptions {
+                compression,
+                quantize: bytes[SAVE_MAGIC.len() + 3] & QUANTIZED_FLAG != 0,
+            },
+        }))
+    }
+}
@@ -0,0 +1,44 @@
+use {
+    crate::World,
+    rand_chacha::ChaCha12Rng,
+    serde::{Deserialize, Serialize},
+};
+
+/// The world as it's written to JSON, with the random number generator's
+/// state next to the world's fields
+#[derive(Serialize)]
+struct JsonWorldRef<'a> {
+    #[serde(flatten)]
+    world: &'a World,
+    rng:   &'a ChaCha12Rng,
+}
+
+#[derive(Deserialize)]
+struct JsonWorld {
+    #[serde(flatten)]
+    world: World,
+    /// Missing from JSON worlds written before it was saved, which continue
+    /// from their seed instead
+    #[serde(default)]
+    rng:   Option<ChaCha12Rng>,
+}
+
+/// The whole world as indented JSON, meant to be readable and editable by
+/// hand. Like the binary format it doesn't store what can be derived, like
+/// the cells' coordinates or the altitude range, and it stores the random
+/// number generator's state so a loaded world continues like the saved one
+/// would have.
+pub fn to_json(world: &World) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(&JsonWorldRef {
+        world,
+        rng: &world.rng,
+    })
+}
+
+pub fn from_json(json: &str) -> Result<World, serde_json::Error> {
+    let JsonWorld { mut world, rng } = serde_json::from_str(json)?;
+    if let Some(rng) = rng {
+        world.rng = rng;
+    }
+    Ok(world)
+}
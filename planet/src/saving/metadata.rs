@@ -0,0 +1,186 @@
+use {
+    crate::{
+        map::{self, MapStyle, WorldView},
+        world_manager::LoadError,
+        World,
+    },
+    bevy::utils::HashSet,
+    serde::{Deserialize, Serialize},
+    std::time::{SystemTime, UNIX_EPOCH},
+};
+
+/// First version with a [`SaveMetadata`] block between the header and the
+/// body
+pub const METADATA_SAVE_VERSION: u16 = 4;
+
+/// What the player knows a world by, kept alongside it and saved in its
+/// metadata
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorldInfo {
+    pub title:       String,
+    pub description: String,
+    /// Seconds since the Unix epoch
+    pub created:     u64,
+}
+impl Default for WorldInfo {
+    fn default() -> Self {
+        WorldInfo {
+            title:       String::new(),
+            description: String::new(),
+            created:     SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_secs()),
+        }
+    }
+}
+impl WorldInfo {
+    /// Creation time as `YYYY-MM-DD HH:MM UTC`
+    #[must_use]
+    pub fn created_utc(&self) -> String {
+        const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+        let (days, seconds) = (
+            self.created / SECONDS_PER_DAY,
+            self.created % SECONDS_PER_DAY,
+        );
+
+        // Civil date from days since 1970-01-01, see
+        // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = days as i64 + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        format!(
+            "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+            seconds / 3600,
+            seconds % 3600 / 60
+        )
+    }
+}
+
+/// Small RGB image of the biome view
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavePreview {
+    pub width:  u32,
+    pub height: u32,
+    /// 8-bit RGB pixels, row by row from the north edge
+    pub pixels: Vec<u8>,
+}
+impl SavePreview {
+    /// Previews are scaled down by whole steps until they fit in this size
+    pub const MAX_SIZE: (u32, u32) = (128, 64);
+
+    #[must_use]
+    pub fn new(world: &World) -> SavePreview {
+        let (max_width, max_height) = SavePreview::MAX_SIZE;
+        let step = u32::max(
+            world.width.div_ceil(max_width),
+            world.height.div_ceil(max_height),
+        )
+        .max(1);
+        let width = world.width.div_ceil(step);
+        let height = world.height.div_ceil(step);
+
+        let overlays = HashSet::new();
+        let style = MapStyle::default();
+        let mut pixels = Vec::with_capacity((width * height * 3) as usize);
+        for preview_y in 0..height {
+            let y = (world.height - 1 - preview_y * step) as usize;
+            for preview_x in 0..width {
+                let cell = &world.terrain[y][(preview_x * step) as usize];
+                let color = map::cell_color(world, cell, WorldView::Biomes, &overlays, &style);
+                pixels.extend(color.as_rgb_u8());
+            }
+        }
+
+        SavePreview {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Whether the preview has pixels and exactly as many as its size needs
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.width > 0
+            && self.height > 0
+            && (self.width as usize)
+                .checked_mul(self.height as usize)
+                .and_then(|pixels| pixels.checked_mul(3))
+                == Some(self.pixels.len())
+    }
+}
+
+/// Describes a save without its terrain, see
+/// [`WorldManager::peek_save`](crate::WorldManager::peek_save)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveMetadata {
+    pub info:      WorldInfo,
+    pub seed:      u32,
+    pub width:     u32,
+    pub height:    u32,
+    pub iteration: u64,
+    pub preview:   SavePreview,
+}
+impl SaveMetadata {
+    #[must_use]
+    pub fn new(world: &World, info: &WorldInfo) -> SaveMetadata {
+        SaveMetadata {
+            info:      info.clone(),
+            seed:      world.seed,
+            width:     world.width,
+            height:    world.height,
+            iteration: world.iteration as u64,
+            preview:   SavePreview::new(world),
+        }
+    }
+
+    /// The metadata block, prefixed with its length so readers can skip to
+    /// the body
+    pub fn to_block(&self) -> Result<Vec<u8>, postcard::Error> {
+        let serialized = postcard::to_stdvec(self)?;
+        let mut block = Vec::with_capacity(4 + serialized.len());
+        block.extend_from_slice(&(serialized.len() as u32).to_le_bytes());
+        block.extend(serialized);
+        Ok(block)
+    }
+
+    /// Length of the metadata block starting with the given bytes
+    pub fn block_length(bytes: &[u8]) -> Result<usize, LoadError> {
+        match bytes {
+            [a, b, c, d, ..] => Ok(4 + u32::from_le_bytes([*a, *b, *c, *d]) as usize),
+            _ => Err(LoadError::InvalidSave(
+                postcard::Error::DeserializeUnexpectedEnd,
+            )),
+        }
+    }
+
+    /// Reads the metadata block at the start of the bytes, returning the bytes
+    /// that follow it. Fails if the preview's size doesn't match its pixels.
+    pub fn from_block(bytes: &[u8]) -> Result<(SaveMetadata, &[u8]), LoadError> {
+        let length = SaveMetadata::block_length(bytes)?;
+        let Some(block) = bytes.get(4..length) else {
+            return Err(LoadError::InvalidSave(
+                postcard::Error::DeserializeUnexpectedEnd,
+            ));
+        };
+        let metadata: SaveMetadata = postcard::from_bytes(block).map_err(LoadError::InvalidSave)?;
+        if !metadata.preview.is_valid() {
+            return Err(LoadError::InvalidSave(
+                postcard::Error::DeserializeBadEncoding,
+            ));
+        }
+        Ok((metadata, &bytes[length..]))
+    }
+}
@@ -0,0 +1,273 @@
+//! Upgrades saves written by older versions of the save format.
+//!
+//! Every past layout of the body keeps its own copy of the structs it was
+//! serialized with, which are decoded as they were written and then upgraded
+//! until they match the current `World`. Versions that only changed what
+//! surrounds the body reuse the structs of the version that last changed it:
+//!
+//! - version 0 wrote a `WorldV0`, without a header
+//! - version 1 wrote a `WorldV1`
+//! - version 2 added compression, and quantized saves writing a
+//!   `QuantizedWorldV2` instead
+//! - version 3 added a `RuntimeStateV3` after the world
+//! - version 4 added the metadata block before the body
+//! - version 5 added the checksum after the body
+//! - version 6 streams the body in records, see [`super::stream`]
+//!
+//! When the save format changes, freeze the current layout here, bump
+//! [`CURRENT_SAVE_VERSION`](super::CURRENT_SAVE_VERSION) and add a step to the
+//! chain.
+
+use {
+    crate::{
+        saving::SaveHeader,
+        world_manager::LoadError,
+        BiomeType,
+        ResourceType,
+        TerrainCell,
+        World,
+    },
+    bevy::{log::info, math::Vec2, utils::default},
+    rand_chacha::ChaCha12Rng,
+    serde::Deserialize,
+};
+
+/// Version 0, the format written before saves had a header
+#[derive(Deserialize)]
+struct WorldV0 {
+    width:             u32,
+    height:            u32,
+    seed:              u32,
+    terrain:           Vec<Vec<TerrainCellV0>>,
+    continent_offsets: [Vec2; 12],
+    continent_sizes:   [Vec2; 12],
+    iteration:         usize,
+}
+
+#[derive(Deserialize)]
+struct TerrainCellV0 {
+    altitude:        f32,
+    rainfall:        f32,
+    temperature:     f32,
+    local_iteration: usize,
+    // Version 0 only knew the first eight biome types, which haven't been
+    // reordered since
+    biome_presences: Vec<(BiomeType, f32)>,
+}
+
+/// Versions 1 to 5, which only differ in what surrounds the world
+#[derive(Deserialize)]
+struct WorldV1 {
+    width:             u32,
+    height:            u32,
+    seed:              u32,
+    terrain:           Vec<Vec<TerrainCellV1>>,
+    continent_offsets: [Vec2; 12],
+    continent_sizes:   [Vec2; 12],
+    iteration:         usize,
+    resource_scarcity: f32,
+}
+
+#[derive(Deserialize)]
+struct TerrainCellV1 {
+    altitude:        f32,
+    rainfall:        f32,
+    temperature:     f32,
+    local_iteration: usize,
+    biome_presences: Vec<(BiomeType, f32)>,
+    soil_fertility:  f32,
+    arability:       f32,
+    resources:       Vec<(ResourceType, f32)>,
+}
+
+/// Quantized saves of versions 2 to 5, laid out like `WorldV1` except for
+/// the quantized cells
+#[derive(Deserialize)]
+struct QuantizedWorldV2 {
+    width:             u32,
+    height:            u32,
+    seed:              u32,
+    terrain:           Vec<Vec<QuantizedTerrainCellV2>>,
+    continent_offsets: [Vec2; 12],
+    continent_sizes:   [Vec2; 12],
+    iteration:         usize,
+    resource_scarcity: f32,
+}
+
+/// Altitude, rainfall and temperature as 16 bit steps between the bounds of
+/// `QuantizedTerrainCellV2::ALTITUDE` and its siblings
+#[derive(Deserialize)]
+struct QuantizedTerrainCellV2 {
+    altitude:        u16,
+    rainfall:        u16,
+    temperature:     u16,
+    local_iteration: usize,
+    biome_presences: Vec<(BiomeType, f32)>,
+    soil_fertility:  f32,
+    arability:       f32,
+    resources:       Vec<(ResourceType, f32)>,
+}
+
+/// Saved after the world since version 3
+#[derive(Deserialize)]
+struct RuntimeStateV3 {
+    rng: ChaCha12Rng,
+}
+
+/// Decodes an uncompressed save body written with the format version in its
+/// header and upgrades it to the current `World`
+pub(crate) fn deserialize_world(header: SaveHeader, bytes: &[u8]) -> Result<World, LoadError> {
+    match header.version {
+        0 => {
+            let world: WorldV0 = postcard::from_bytes(bytes).map_err(LoadError::InvalidSave)?;
+            upgrade_v0(world)
+        },
+        // Before version 3 the RNG wasn't saved, so it restarts from the seed
+        1 | 2 => deserialize_v1(header, bytes).map(|(world, _)| upgrade_v1(world)),
+        3..=5 => {
+            let (world, rest) = deserialize_v1(header, bytes)?;
+            let runtime_state: RuntimeStateV3 =
+                postcard::from_bytes(rest).map_err(LoadError::InvalidSave)?;
+            Ok(upgrade_v3(upgrade_v1(world), runtime_state))
+        },
+        version => Err(LoadError::UnsupportedVersion(version)),
+    }
+}
+
+/// Decodes the world at the start of a body of version 1 to 5, returning the
+/// bytes that follow it
+fn deserialize_v1(header: SaveHeader, bytes: &[u8]) -> Result<(WorldV1, &[u8]), LoadError> {
+    if header.options.quantize {
+        let (world, rest): (QuantizedWorldV2, _) =
+            postcard::take_from_bytes(bytes).map_err(LoadError::InvalidSave)?;
+        Ok((upgrade_v2(world), rest))
+    } else {
+        postcard::take_from_bytes(bytes).map_err(LoadError::InvalidSave)
+    }
+}
+
+/// Version 1 added arability and resources, which are derived from the rest of
+/// the terrain, as well as the world's resource scarcity.
+fn upgrade_v0(old: WorldV0) -> Result<World, LoadError> {
+    info!("Upgrading save from version 0");
+    let terrain = old
+        .terrain
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|cell| TerrainCell {
+                    altitude: cell.altitude,
+                    rainfall: cell.rainfall,
+                    temperature: cell.temperature,
+                    local_iteration: cell.local_iteration,
+                    biome_presences: cell.biome_presences,
+                    ..default()
+                })
+                .collect()
+        })
+        .collect();
+    let mut world = World::with_terrain(old.width, old.height, old.seed, terrain);
+    world.continent_offsets = old.continent_offsets;
+    world.continent_sizes = old.continent_sizes;
+    world.iteration = old.iteration;
+    world.complete_terrain();
+    // Deriving the new fields walks the terrain by the world's size
+    world.validate().map_err(LoadError::InvalidWorld)?;
+
+    // Nobody listens to the progress of a migration
+    let (progress_sender, _) = crossbeam_channel::bounded(0);
+    let coast_distances = world.coast_distances();
+    world.generate_arability(&progress_sender, &coast_distances);
+    world.generate_resources(&progress_sender);
+
+    Ok(world)
+}
+
+/// Versions 1 to 5 stored everything the current `World` does, except for the
+/// RNG
+fn upgrade_v1(old: WorldV1) -> World {
+    let terrain = old
+        .terrain
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|cell| TerrainCell {
+                    altitude: cell.altitude,
+                    rainfall: cell.rainfall,
+                    temperature: cell.temperature,
+                    local_iteration: cell.local_iteration,
+                    biome_presences: cell.biome_presences,
+                    soil_fertility: cell.soil_fertility,
+                    arability: cell.arability,
+                    resources: cell.resources,
+                    ..default()
+                })
+                .collect()
+        })
+        .collect();
+    let mut world = World::with_terrain(old.width, old.height, old.seed, terrain);
+    world.continent_offsets = old.continent_offsets;
+    world.continent_sizes = old.continent_sizes;
+    world.iteration = old.iteration;
+    world.resource_scarcity = old.resource_scarcity;
+    world.complete_terrain();
+    world
+}
+
+impl QuantizedTerrainCellV2 {
+    const ALTITUDE: (f32, f32) = (-15000.0, 15000.0);
+    const RAINFALL: (f32, f32) = (0.0, 13000.0);
+    const TEMPERATURE: (f32, f32) = (-35.0, 30.0);
+
+    fn dequantize(value: u16, (min, max): (f32, f32)) -> f32 {
+        min + f32::from(value) / f32::from(u16::MAX) * (max - min)
+    }
+}
+
+/// Quantized saves of version 2 to 5 are read as if they had full floats
+fn upgrade_v2(old: QuantizedWorldV2) -> WorldV1 {
+    let terrain = old
+        .terrain
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|cell| TerrainCellV1 {
+                    altitude:        QuantizedTerrainCellV2::dequantize(
+                        cell.altitude,
+                        QuantizedTerrainCellV2::ALTITUDE,
+                    ),
+                    rainfall:        QuantizedTerrainCellV2::dequantize(
+                        cell.rainfall,
+                        QuantizedTerrainCellV2::RAINFALL,
+                    ),
+                    temperature:     QuantizedTerrainCellV2::dequantize(
+                        cell.temperature,
+                        QuantizedTerrainCellV2::TEMPERATURE,
+                    ),
+                    local_iteration: cell.local_iteration,
+                    biome_presences: cell.biome_presences,
+                    soil_fertility:  cell.soil_fertility,
+                    arability:       cell.arability,
+                    resources:       cell.resources,
+                })
+                .collect()
+        })
+        .collect();
+    WorldV1 {
+        width: old.width,
+        height: old.height,
+        seed: old.seed,
+        terrain,
+        continent_offsets: old.continent_offsets,
+        continent_sizes: old.continent_sizes,
+        iteration: old.iteration,
+        resource_scarcity: old.resource_scarcity,
+    }
+}
+
+/// Version 3 saved the RNG, so the world continues like the saved one would
+/// have
+fn upgrade_v3(mut world: World, runtime_state: RuntimeStateV3) -> World {
+    world.rng = runtime_state.rng;
+    world
+}
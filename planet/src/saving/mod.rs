@@ -1,2 +1,23 @@
+pub mod atomic;
+pub mod autosave;
+pub use autosave::{AutosaveSettings, Autosaves};
+pub mod checksum;
+pub mod compression;
+pub mod format;
+pub use format::SaveFormat;
+pub mod header;
+pub use header::{SaveHeader, CURRENT_SAVE_VERSION, LEGACY_SAVE_VERSION, SAVE_MAGIC};
+pub mod json;
+pub mod metadata;
+pub use metadata::{SaveMetadata, WorldInfo};
+pub mod migration;
+pub mod options;
+pub use options::{SaveCompression, SaveOptions};
+pub(crate) mod quantized;
+pub mod recipe;
+pub use recipe::WorldRecipe;
+pub(crate) mod runtime_state;
+pub mod stream;
+pub mod tabular;
 // pub mod terrain_cell;
 pub mod world;
@@ -0,0 +1,28 @@
+use crate::macros::iterable_enum;
+
+iterable_enum!(SaveCompression { None, Deflate });
+
+impl SaveCompression {
+    /// Balances save time against size. Worlds compress well enough that
+    /// higher levels barely make files smaller.
+    pub const DEFLATE_LEVEL: u8 = 6;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveOptions {
+    pub compression: SaveCompression,
+    /// Store altitude, rainfall and temperature as 16 bit values spread over
+    /// their possible ranges instead of full floats. Loses precision below
+    /// half a metre of altitude, a fifth of a millimetre of rainfall and a
+    /// thousandth of a degree.
+    pub quantize:    bool,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            compression: SaveCompression::Deflate,
+            quantize:    false,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use {
+    crate::{BiomeType, ResourceType, TerrainCell, World},
+    bevy::utils::default,
+    serde::{Deserialize, Serialize},
+    std::borrow::Cow,
+};
+
+/// A cell whose altitude, rainfall and temperature are stored as 16 bit steps
+/// between their minimum and maximum, as the terrain records of quantized
+/// saves hold them, see [`crate::saving::stream`]
+#[derive(Serialize, Deserialize)]
+pub(crate) struct QuantizedTerrainCell<'a> {
+    altitude:        u16,
+    rainfall:        u16,
+    temperature:     u16,
+    local_iteration: usize,
+    biome_presences: Cow<'a, [(BiomeType, f32)]>,
+    soil_fertility:  f32,
+    arability:       f32,
+    resources:       Cow<'a, [(ResourceType, f32)]>,
+}
+
+fn quantize(value: f32, min: f32, max: f32) -> u16 {
+    ((value.clamp(min, max) - min) / (max - min) * f32::from(u16::MAX)).round() as u16
+}
+
+fn dequantize(value: u16, min: f32, max: f32) -> f32 {
+    min + f32::from(value) / f32::from(u16::MAX) * (max - min)
+}
+
+impl<'a> QuantizedTerrainCell<'a> {
+    pub(crate) fn new(cell: &'a TerrainCell) -> QuantizedTerrainCell<'a> {
+        QuantizedTerrainCell {
+            altitude:        quantize(cell.altitude, World::MIN_ALTITUDE, World::MAX_ALTITUDE),
+            rainfall:        quantize(cell.rainfall, World::MIN_RAINFALL, World::MAX_RAINFALL),
+            temperature:     quantize(
+                cell.temperature,
+                World::MIN_TEMPERATURE,
+                World::MAX_TEMPERATURE,
+            ),
+            local_iteration: cell.local_iteration,
+            biome_presences: Cow::Borrowed(&cell.biome_presences),
+            soil_fertility:  cell.soil_fertility,
+            arability:       cell.arability,
+            resources:       Cow::Borrowed(&cell.resources),
+        }
+    }
+
+    pub(crate) fn into_cell(self) -> TerrainCell {
+        TerrainCell {
+            altitude: dequantize(self.altitude, World::MIN_ALTITUDE, World::MAX_ALTITUDE),
+            rainfall: dequantize(self.rainfall, World::MIN_RAINFALL, World::MAX_RAINFALL),
+            temperature: dequantize(
+                self.temperature,
+                World::MIN_TEMPERATURE,
+                World::MAX_TEMPERATURE,
+            ),
+            local_iteration: self.local_iteration,
+            biome_presences: self.biome_presences.into_owned(),
+            soil_fertility: self.soil_fertility,
+            arability: self.arability,
+            resources: self.resources.into_owned(),
+            ..default()
+        }
+    }
+}
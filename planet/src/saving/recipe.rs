@@ -0,0 +1,73 @@
+use {
+    crate::World,
+    serde::{de::Error, Deserialize, Deserializer, Serialize},
+};
+
+/// Version of the world generator that recipes are written with. Worlds
+/// regenerated from a recipe written by another version may differ from
+/// the original.
+pub const GENERATOR_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Everything needed to regenerate a world, as a small text file. Only
+/// describes the world as it was generated, not what happened to it since.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldRecipe {
+    pub generator_version: String,
+    pub seed:              u32,
+    pub width:             u32,
+    pub height:            u32,
+    #[serde(
+        default = "default_resource_scarcity",
+        deserialize_with = "deserialize_resource_scarcity"
+    )]
+    pub resource_scarcity: f32,
+}
+
+fn default_resource_scarcity() -> f32 {
+    World::DEFAULT_RESOURCE_SCARCITY
+}
+
+fn deserialize_resource_scarcity<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<f32, D::Error> {
+    let scarcity = f32::deserialize(deserializer)?;
+    World::validate_resource_scarcity(scarcity).map_err(D::Error::custom)?;
+    Ok(scarcity)
+}
+
+impl WorldRecipe {
+    pub const EXTENSION: &'static str = "ron";
+
+    #[must_use]
+    pub fn new(world: &World) -> WorldRecipe {
+        WorldRecipe {
+            generator_version: String::from(GENERATOR_VERSION),
+            seed:              world.seed,
+            width:             world.width,
+            height:            world.height,
+            resource_scarcity: world.resource_scarcity,
+        }
+    }
+
+    pub fn from_ron(recipe: &str) -> Result<WorldRecipe, ron::error::SpannedError> {
+        ron::from_str(recipe)
+    }
+
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    /// Whether the recipe was written by this version of the generator
+    #[must_use]
+    pub fn matches_generator(&self) -> bool {
+        self.generator_version == GENERATOR_VERSION
+    }
+
+    /// Creates the world described by the recipe, ready to be generated
+    #[must_use]
+    pub fn world(&self) -> World {
+        let mut world = World::async_new(self.width, self.height, self.seed);
+        world.resource_scarcity = self.resource_scarcity;
+        world
+    }
+}
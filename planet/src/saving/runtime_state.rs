@@ -0,0 +1,26 @@
+use {
+    crate::World,
+    rand_chacha::ChaCha12Rng,
+    serde::{Deserialize, Serialize},
+};
+
+/// State of a running simulation that isn't part of the world's layout,
+/// saved after the world so a loaded world continues exactly like the one
+/// that was saved. Everything else the simulation advances, like the world's
+/// iteration and the cells' local iterations, is part of the world itself.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RuntimeState {
+    rng: ChaCha12Rng,
+}
+
+impl RuntimeState {
+    pub(crate) fn new(world: &World) -> RuntimeState {
+        RuntimeState {
+            rng: world.rng.clone(),
+        }
+    }
+
+    pub(crate) fn apply(self, world: &mut World) {
+        world.rng = self.rng;
+    }
+}
@@ -0,0 +1,224 @@
+//! The body of saves since version 6, written and read a few rows at a time so
+//! that neither a whole serialized world nor a whole file is ever held in
+//! memory.
+//!
+//! The body is a sequence of records, each a `u32` little endian length
+//! followed by that many bytes of postcard. The first record is the
+//! `WorldLayout`, followed by chunks of up to [`ROWS_PER_CHUNK`] terrain rows
+//! until there are as many as the world is high, and finally the
+//! `RuntimeState`. Quantized saves store their rows as quantized cells.
+
+use {
+    crate::{
+        saving::{quantized::QuantizedTerrainCell, runtime_state::RuntimeState},
+        world::send_progress,
+        world_manager::{LoadError, SaveError},
+        BiomeType,
+        ResourceType,
+        TerrainCell,
+        World,
+    },
+    bevy::{math::Vec2, utils::default},
+    crossbeam_channel::Sender,
+    serde::{de::DeserializeOwned, Deserialize, Serialize},
+    std::{
+        borrow::Cow,
+        io::{self, Read, Write},
+    },
+};
+
+/// First version whose body is streamed in chunks of rows
+pub const STREAMED_SAVE_VERSION: u16 = 6;
+/// Rows per terrain record. Large enough for records to compress well, small
+/// enough that a record of even a very wide world stays small.
+pub const ROWS_PER_CHUNK: usize = 16;
+
+/// Everything of a `World` that is saved, except for its terrain
+#[derive(Serialize, Deserialize)]
+struct WorldLayout {
+    width:             u32,
+    height:            u32,
+    seed:              u32,
+    continent_offsets: [Vec2; World::NUM_CONTINENTS as usize],
+    continent_sizes:   [Vec2; World::NUM_CONTINENTS as usize],
+    iteration:         usize,
+    resource_scarcity: f32,
+}
+
+/// A cell as the terrain records of saves that aren't quantized hold it. Kept
+/// apart from `TerrainCell`, so that changing the cell doesn't silently change
+/// the save format.
+#[derive(Serialize, Deserialize)]
+struct SavedTerrainCell<'a> {
+    altitude:        f32,
+    rainfall:        f32,
+    temperature:     f32,
+    local_iteration: usize,
+    biome_presences: Cow<'a, [(BiomeType, f32)]>,
+    soil_fertility:  f32,
+    arability:       f32,
+    resources:       Cow<'a, [(ResourceType, f32)]>,
+}
+
+impl<'a> SavedTerrainCell<'a> {
+    fn new(cell: &'a TerrainCell) -> SavedTerrainCell<'a> {
+        SavedTerrainCell {
+            altitude:        cell.altitude,
+            rainfall:        cell.rainfall,
+            temperature:     cell.temperature,
+            local_iteration: cell.local_iteration,
+            biome_presences: Cow::Borrowed(&cell.biome_presences),
+            soil_fertility:  cell.soil_fertility,
+            arability:       cell.arability,
+            resources:       Cow::Borrowed(&cell.resources),
+        }
+    }
+
+    fn into_cell(self) -> TerrainCell {
+        TerrainCell {
+            altitude: self.altitude,
+            rainfall: self.rainfall,
+            temperature: self.temperature,
+            local_iteration: self.local_iteration,
+            biome_presences: self.biome_presences.into_owned(),
+            soil_fertility: self.soil_fertility,
+            arability: self.arability,
+            resources: self.resources.into_owned(),
+            ..default()
+        }
+    }
+}
+
+/// Writes the records of a body, counting how many bytes were written
+struct RecordWriter<'w, W: Write> {
+    writer:  &'w mut W,
+    written: usize,
+}
+
+impl<W: Write> RecordWriter<'_, W> {
+    fn write<T: Serialize>(&mut self, record: &T) -> Result<(), SaveError> {
+        let serialized = postcard::to_stdvec(record).map_err(SaveError::SerializationError)?;
+        let length = u32::try_from(serialized.len()).map_err(|_| {
+            SaveError::FailedToWrite(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Save record is too large",
+            ))
+        })?;
+        self.writer.write_all(&length.to_le_bytes())?;
+        self.writer.write_all(&serialized)?;
+        self.written += 4 + serialized.len();
+        Ok(())
+    }
+}
+
+/// Writes the body of a save, returning its length before compression
+pub(crate) fn write_body<W: Write>(
+    world: &World,
+    quantize: bool,
+    writer: &mut W,
+) -> Result<usize, SaveError> {
+    let mut records = RecordWriter { writer, written: 0 };
+
+    records.write(&WorldLayout {
+        width:             world.width,
+        height:            world.height,
+        seed:              world.seed,
+        continent_offsets: world.continent_offsets,
+        continent_sizes:   world.continent_sizes,
+        iteration:         world.iteration,
+        resource_scarcity: world.resource_scarcity,
+    })?;
+    for rows in world.terrain.chunks(ROWS_PER_CHUNK) {
+        if quantize {
+            let rows: Vec<Vec<QuantizedTerrainCell>> = rows
+                .iter()
+                .map(|row| row.iter().map(QuantizedTerrainCell::new).collect())
+                .collect();
+            records.write(&rows)?;
+        } else {
+            let rows: Vec<Vec<SavedTerrainCell>> = rows
+                .iter()
+                .map(|row| row.iter().map(SavedTerrainCell::new).collect())
+                .collect();
+            records.write(&rows)?;
+        }
+    }
+    records.write(&RuntimeState::new(world))?;
+
+    Ok(records.written)
+}
+
+/// Reads the next record, which is also how the metadata block is laid out
+pub(crate) fn read_record<T: DeserializeOwned, R: Read>(reader: &mut R) -> Result<T, LoadError> {
+    let unexpected_end = |err: io::Error| {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            LoadError::InvalidSave(postcard::Error::DeserializeUnexpectedEnd)
+        } else {
+            LoadError::MissingSave(err)
+        }
+    };
+
+    let mut length = [0; 4];
+    reader.read_exact(&mut length).map_err(unexpected_end)?;
+    let length = u32::from_le_bytes(length);
+
+    // Grows with what is actually read, so a corrupted length can't make it
+    // allocate more than the save holds
+    let mut buffer = vec![];
+    _ = reader
+        .by_ref()
+        .take(u64::from(length))
+        .read_to_end(&mut buffer)
+        .map_err(unexpected_end)?;
+    if buffer.len() < length as usize {
+        return Err(LoadError::InvalidSave(
+            postcard::Error::DeserializeUnexpectedEnd,
+        ));
+    }
+
+    postcard::from_bytes(&buffer).map_err(LoadError::InvalidSave)
+}
+
+/// Reads the body of a save, reporting how much of the terrain was read. The
+/// world isn't validated.
+pub(crate) fn read_body<R: Read>(
+    quantized: bool,
+    reader: &mut R,
+    progress_sender: &Sender<(f32, String)>,
+) -> Result<World, LoadError> {
+    let layout: WorldLayout = read_record(reader)?;
+
+    let mut terrain: Vec<Vec<TerrainCell>> = vec![];
+    while terrain.len() < layout.height as usize {
+        if quantized {
+            let rows: Vec<Vec<QuantizedTerrainCell>> = read_record(reader)?;
+            terrain.extend(rows.into_iter().map(|row| {
+                row.into_iter()
+                    .map(QuantizedTerrainCell::into_cell)
+                    .collect()
+            }));
+        } else {
+            let rows: Vec<Vec<SavedTerrainCell>> = read_record(reader)?;
+            terrain.extend(
+                rows.into_iter()
+                    .map(|row| row.into_iter().map(SavedTerrainCell::into_cell).collect()),
+            );
+        }
+        send_progress(
+            progress_sender,
+            terrain.len() as f32 / layout.height as f32,
+            "Loading terrain",
+        );
+    }
+
+    let mut world = World::with_terrain(layout.width, layout.height, layout.seed, terrain);
+    world.continent_offsets = layout.continent_offsets;
+    world.continent_sizes = layout.continent_sizes;
+    world.iteration = layout.iteration;
+    world.resource_scarcity = layout.resource_scarcity;
+    world.complete_terrain();
+
+    let runtime_state: RuntimeState = read_record(reader)?;
+    runtime_state.apply(&mut world);
+    Ok(world)
+}
@@ -0,0 +1,54 @@
+use {
+    crate::{BiomeType, World},
+    std::io::Write,
+};
+
+/// Columns before the biome presences
+pub const CELL_COLUMNS: [&str; 7] = [
+    "x",
+    "y",
+    "latitude",
+    "longitude",
+    "altitude",
+    "rainfall",
+    "temperature",
+];
+
+/// Writes one row per cell, from the south-west corner row by row, with the
+/// [`CELL_COLUMNS`] followed by the presence of every biome type. Latitude and
+/// longitude are in degrees.
+pub fn write_csv<W: Write>(world: &World, writer: W) -> Result<(), csv::Error> {
+    let mut writer = csv::Writer::from_writer(writer);
+
+    writer.write_record(
+        CELL_COLUMNS
+            .iter()
+            .copied()
+            .chain(BiomeType::iterator().map(|biome| biome.stats().name)),
+    )?;
+
+    let mut record = Vec::with_capacity(CELL_COLUMNS.len() + BiomeType::ITEM_COUNT);
+    for cell in world.terrain.iter().flatten() {
+        record.clear();
+        let longitude = cell.x as f32 / world.width as f32 * 360.0 - 180.0;
+        record.extend([
+            cell.x.to_string(),
+            cell.y.to_string(),
+            world.latitude(cell.y).to_degrees().to_string(),
+            longitude.to_string(),
+            cell.altitude.to_string(),
+            cell.rainfall.to_string(),
+            cell.temperature.to_string(),
+        ]);
+        record.extend(BiomeType::iterator().map(|biome| {
+            cell.biome_presences
+                .iter()
+                .find(|(present_biome, _)| present_biome == biome)
+                .map_or(0.0, |(_, presence)| *presence)
+                .to_string()
+        }));
+        writer.write_record(&record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use {
     crate::{TerrainCell, World},
     bevy::prelude::debug,
-    rand::{rngs::StdRng, SeedableRng},
+    rand::SeedableRng,
+    rand_chacha::ChaCha12Rng,
     serde::{
         de::{Error, MapAccess, SeqAccess, Visitor},
         Deserialize,
@@ -30,6 +31,54 @@ impl Default for WorldTerrainAttributes {
     }
 }
 
+impl World {
+    /// Fills in what saves don't store: the cells' coordinates and the
+    /// world's attribute ranges
+    pub(crate) fn complete_terrain(&mut self) {
+        let world_attributes = &mut WorldTerrainAttributes::default();
+        let world_attributes =
+            self.terrain
+                .iter()
+                .flatten()
+                .fold(world_attributes, |attributes, cell| {
+                    if cell.altitude > attributes.max_altitude {
+                        attributes.max_altitude = cell.altitude;
+                    }
+                    if cell.altitude < attributes.min_altitude {
+                        attributes.min_altitude = cell.altitude;
+                    }
+
+                    if cell.rainfall > attributes.max_rainfall {
+                        attributes.max_rainfall = cell.rainfall;
+                    }
+                    if cell.rainfall < attributes.min_rainfall {
+                        attributes.min_rainfall = cell.rainfall;
+                    }
+
+                    if cell.temperature > attributes.max_temperature {
+                        attributes.max_temperature = cell.temperature;
+                    }
+                    if cell.temperature < attributes.min_temperature {
+                        attributes.min_temperature = cell.temperature;
+                    }
+                    attributes
+                });
+        self.max_altitude = world_attributes.max_altitude;
+        self.min_altitude = world_attributes.min_altitude;
+        self.max_rainfall = world_attributes.max_rainfall;
+        self.min_rainfall = world_attributes.min_rainfall;
+        self.max_temperature = world_attributes.max_temperature;
+        self.min_temperature = world_attributes.min_temperature;
+
+        for (y, terrain_row) in self.terrain.iter_mut().enumerate() {
+            for (x, terrain_cell) in terrain_row.iter_mut().enumerate() {
+                terrain_cell.x = x;
+                terrain_cell.y = y;
+            }
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for World {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -45,6 +94,7 @@ impl<'de> Deserialize<'de> for World {
             ContinentOffsets,
             ContinentSizes,
             Iteration,
+            ResourceScarcity,
         }
 
         struct WorldVisitor;
@@ -90,34 +140,9 @@ impl<'de> Deserialize<'de> for World {
                     .ok_or_else(|| Error::invalid_length(6, &self))?;
                 debug!("Iteration bbbbb");
 
-                let world_attributes = &mut WorldTerrainAttributes::default();
-                let world_attributes =
-                    terrain
-                        .iter()
-                        .flatten()
-                        .fold(world_attributes, |attributes, cell| {
-                            if cell.altitude > attributes.max_altitude {
-                                attributes.max_altitude = cell.altitude;
-                            }
-                            if cell.altitude < attributes.min_altitude {
-                                attributes.min_altitude = cell.altitude;
-                            }
-
-                            if cell.rainfall > attributes.max_rainfall {
-                                attributes.max_rainfall = cell.rainfall;
-                            }
-                            if cell.rainfall < attributes.min_rainfall {
-                                attributes.min_rainfall = cell.rainfall;
-                            }
-
-                            if cell.temperature > attributes.max_temperature {
-                                attributes.max_temperature = cell.temperature;
-                            }
-                            if cell.temperature < attributes.min_temperature {
-                                attributes.min_temperature = cell.temperature;
-                            }
-                            attributes
-                        });
+                let resource_scarcity = seq
+                    .next_element()?
+                    .ok_or_else(|| Error::invalid_length(7, &self))?;
 
                 debug!("Constructing world");
                 let mut world = World {
@@ -128,29 +153,19 @@ impl<'de> Deserialize<'de> for World {
                     continent_offsets,
                     continent_sizes,
 
-                    max_altitude: world_attributes.max_altitude,
-                    min_altitude: world_attributes.min_altitude,
-                    max_rainfall: world_attributes.max_rainfall,
-                    min_rainfall: world_attributes.min_rainfall,
-                    max_temperature: world_attributes.max_temperature,
-                    min_temperature: world_attributes.min_temperature,
+                    max_altitude: World::MIN_ALTITUDE,
+                    min_altitude: World::MAX_ALTITUDE,
+                    max_rainfall: World::MIN_RAINFALL,
+                    min_rainfall: World::MAX_RAINFALL,
+                    max_temperature: World::MIN_TEMPERATURE,
+                    min_temperature: World::MAX_TEMPERATURE,
 
-                    rng: StdRng::seed_from_u64(seed as u64),
+                    rng: ChaCha12Rng::seed_from_u64(seed as u64),
                     iteration,
+                    resource_scarcity,
                 };
-                {
-                    let mut y = 0;
-                    debug!("Completing terrain");
-                    for terrain_row in world.terrain.iter_mut() {
-                        let mut x = 0;
-                        for terrain_cell in terrain_row.iter_mut() {
-                            terrain_cell.x = x;
-                            terrain_cell.y = y;
-                            x += 1;
-                        }
-                        y += 1;
-                    }
-                }
+                debug!("Completing terrain");
+                world.complete_terrain();
                 Ok(world)
             }
 
@@ -165,6 +180,7 @@ impl<'de> Deserialize<'de> for World {
                 let mut continent_offsets = None;
                 let mut continent_widths = None;
                 let mut iteration = None;
+                let mut resource_scarcity = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -200,7 +216,7 @@ impl<'de> Deserialize<'de> for World {
                         },
                         Field::ContinentSizes => {
                             if continent_widths.is_some() {
-                                return Err(Error::duplicate_field("continent_widths"));
+                                return Err(Error::duplicate_field("continent_sizes"));
                             }
                             continent_widths = Some(map.next_value()?);
                         },
@@ -210,57 +226,29 @@ impl<'de> Deserialize<'de> for World {
                             }
                             iteration = Some(map.next_value()?);
                         },
+                        Field::ResourceScarcity => {
+                            if resource_scarcity.is_some() {
+                                return Err(Error::duplicate_field("resource_scarcity"));
+                            }
+                            resource_scarcity = Some(map.next_value()?);
+                        },
                     }
                 }
 
                 let width = width.ok_or_else(|| Error::missing_field("width"))?;
                 let height = height.ok_or_else(|| Error::missing_field("height"))?;
                 let seed = seed.ok_or_else(|| Error::missing_field("seed"))?;
-                let mut terrain: Vec<Vec<TerrainCell>> =
+                let terrain: Vec<Vec<TerrainCell>> =
                     terrain.ok_or_else(|| Error::missing_field("terrain"))?;
 
-                for x in 0..width as usize {
-                    for y in 0..height as usize {
-                        terrain[y][x].x = x;
-                        terrain[y][x].y = y;
-                    }
-                }
-
                 let continent_offsets =
                     continent_offsets.ok_or_else(|| Error::missing_field("continent_offsets"))?;
                 let continent_widths =
-                    continent_widths.ok_or_else(|| Error::missing_field("continent_widths"))?;
-
-                let world_attributes = &mut WorldTerrainAttributes::default();
-                let world_attributes =
-                    terrain
-                        .iter()
-                        .flatten()
-                        .fold(world_attributes, |attributes, cell| {
-                            if cell.altitude > attributes.max_altitude {
-                                attributes.max_altitude = cell.altitude;
-                            }
-                            if cell.altitude < attributes.min_altitude {
-                                attributes.min_altitude = cell.altitude;
-                            }
-
-                            if cell.rainfall > attributes.max_rainfall {
-                                attributes.max_rainfall = cell.rainfall;
-                            }
-                            if cell.rainfall < attributes.min_rainfall {
-                                attributes.min_rainfall = cell.rainfall;
-                            }
-
-                            if cell.temperature > attributes.max_temperature {
-                                attributes.max_temperature = cell.temperature;
-                            }
-                            if cell.temperature < attributes.min_temperature {
-                                attributes.min_temperature = cell.temperature;
-                            }
-                            attributes
-                        });
+                    continent_widths.ok_or_else(|| Error::missing_field("continent_sizes"))?;
 
                 let iteration = iteration.ok_or_else(|| Error::missing_field("iteration"))?;
+                let resource_scarcity =
+                    resource_scarcity.ok_or_else(|| Error::missing_field("resource_scarcity"))?;
 
                 let mut world = World {
                     width,
@@ -270,28 +258,19 @@ impl<'de> Deserialize<'de> for World {
                     continent_offsets,
                     continent_sizes: continent_widths,
 
-                    max_altitude: world_attributes.max_altitude,
-                    min_altitude: world_attributes.min_altitude,
-                    max_rainfall: world_attributes.max_rainfall,
-                    min_rainfall: world_attributes.min_rainfall,
-                    max_temperature: world_attributes.max_temperature,
-                    min_temperature: world_attributes.min_temperature,
+                    max_altitude: World::MIN_ALTITUDE,
+                    min_altitude: World::MAX_ALTITUDE,
+                    max_rainfall: World::MIN_RAINFALL,
+                    min_rainfall: World::MAX_RAINFALL,
+                    max_temperature: World::MIN_TEMPERATURE,
+                    min_temperature: World::MAX_TEMPERATURE,
 
-                    rng: StdRng::seed_from_u64(seed as u64),
+                    rng: ChaCha12Rng::seed_from_u64(seed as u64),
                     iteration,
+                    resource_scarcity,
                 };
-                {
-                    let mut y = 0;
-                    for terrain_row in world.terrain.iter_mut() {
-                        let mut x = 0;
-                        for terrain_cell in terrain_row.iter_mut() {
-                            terrain_cell.x = x;
-                            terrain_cell.y = y;
-                            x += 1;
-                        }
-                        y += 1;
-                    }
-                }
+                debug!("Completing terrain");
+                world.complete_terrain();
                 Ok(world)
             }
         }
@@ -302,7 +281,9 @@ impl<'de> Deserialize<'de> for World {
             "seed",
             "terrain",
             "continent_offsets",
-            "continent_widths",
+            "continent_sizes",
+            "iteration",
+            "resource_scarcity",
         ];
 
         deserializer.deserialize_struct("World", FIELDS, WorldVisitor)
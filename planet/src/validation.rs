@@ -0,0 +1,165 @@
+//! Checks that a world is consistent enough to be simulated and rendered.
+//!
+//! Saves, JSON worlds and imports can describe worlds the generator never
+//! would, which would otherwise only fail later, e.g. when indexing neighbours
+//! past the end of a short row.
+
+use {
+    crate::World,
+    std::{error::Error, fmt::Display},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WorldValidationError {
+    EmptyWorld {
+        width:  u32,
+        height: u32,
+    },
+    WrongRowCount {
+        height: u32,
+        rows:   usize,
+    },
+    WrongRowLength {
+        y:      usize,
+        width:  u32,
+        length: usize,
+    },
+    NonFiniteValue {
+        x:     usize,
+        y:     usize,
+        field: &'static str,
+        value: f32,
+    },
+    InvalidBiomePresences {
+        x:     usize,
+        y:     usize,
+        total: f32,
+    },
+    InvalidResourceScarcity(f32),
+}
+impl Error for WorldValidationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+
+    fn description(&self) -> &str {
+        "description() is deprecated; use Display"
+    }
+
+    fn cause(&self) -> Option<&dyn Error> {
+        self.source()
+    }
+}
+impl Display for WorldValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            WorldValidationError::EmptyWorld { width, height } => {
+                f.write_fmt(format_args!("World has no cells ({width}x{height})"))
+            },
+            WorldValidationError::WrongRowCount { height, rows } => f.write_fmt(format_args!(
+                "World is {height} cells high, but has {rows} rows of terrain"
+            )),
+            WorldValidationError::WrongRowLength { y, width, length } => f.write_fmt(format_args!(
+                "World is {width} cells wide, but row {y} has {length} cells"
+            )),
+            WorldValidationError::NonFiniteValue { x, y, field, value } => {
+                f.write_fmt(format_args!("Cell {x}, {y} has a {field} of {value}"))
+            },
+            WorldValidationError::InvalidBiomePresences { x, y, total } => f.write_fmt(
+                format_args!("Biome presences of cell {x}, {y} add up to {total} instead of 1"),
+            ),
+            WorldValidationError::InvalidResourceScarcity(scarcity) => f.write_fmt(format_args!(
+                "Resource scarcity must be a positive number, not {scarcity}"
+            )),
+        }
+    }
+}
+
+impl World {
+    /// How far the biome presences of a cell may add up to something other
+    /// than 1, to allow for rounding
+    pub const BIOME_PRESENCE_TOLERANCE: f32 = 0.001;
+
+    /// Checks that the resource scarcity can divide the chance of placing a
+    /// resource, which rules out zero, negative and non-finite values
+    pub fn validate_resource_scarcity(scarcity: f32) -> Result<(), WorldValidationError> {
+        if scarcity.is_finite() && scarcity > 0.0 {
+            Ok(())
+        } else {
+            Err(WorldValidationError::InvalidResourceScarcity(scarcity))
+        }
+    }
+
+    /// Checks that the terrain matches the world's size, that the resource
+    /// scarcity is valid, and that every cell has finite values and biome
+    /// presences adding up to 1. Cells without any biome are allowed, as they
+    /// are before biomes are generated.
+    pub fn validate(&self) -> Result<(), WorldValidationError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(WorldValidationError::EmptyWorld {
+                width:  self.width,
+                height: self.height,
+            });
+        }
+        World::validate_resource_scarcity(self.resource_scarcity)?;
+        if self.terrain.len() != self.height as usize {
+            return Err(WorldValidationError::WrongRowCount {
+                height: self.height,
+                rows:   self.terrain.len(),
+            });
+        }
+
+        for (y, row) in self.terrain.iter().enumerate() {
+            if row.len() != self.width as usize {
+                return Err(WorldValidationError::WrongRowLength {
+                    y,
+                    width: self.width,
+                    length: row.len(),
+                });
+            }
+
+            for (x, cell) in row.iter().enumerate() {
+                for (field, value) in [
+                    ("altitude", cell.altitude),
+                    ("rainfall", cell.rainfall),
+                    ("temperature", cell.temperature),
+                    ("soil fertility", cell.soil_fertility),
+                    ("arability", cell.arability),
+                ]
+                .into_iter()
+                .chain(
+                    cell.biome_presences
+                        .iter()
+                        .map(|(_, presence)| ("biome presence", *presence)),
+                )
+                .chain(
+                    cell.resources
+                        .iter()
+                        .map(|(_, amount)| ("resource amount", *amount)),
+                ) {
+                    if !value.is_finite() {
+                        return Err(WorldValidationError::NonFiniteValue { x, y, field, value });
+                    }
+                }
+
+                if cell.biome_presences.is_empty() {
+                    continue;
+                }
+                let total: f32 = cell
+                    .biome_presences
+                    .iter()
+                    .map(|(_, presence)| presence)
+                    .sum();
+                let negative = cell
+                    .biome_presences
+                    .iter()
+                    .any(|(_, presence)| *presence < 0.0);
+                if negative || (total - 1.0).abs() > World::BIOME_PRESENCE_TOLERANCE {
+                    return Err(WorldValidationError::InvalidBiomePresences { x, y, total });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
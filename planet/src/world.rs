@@ -1,5 +1,6 @@
 use {
     crate::{
+        import::{Heightmap, HeightmapOptions},
         math_util::{
             cartesian_coordinates,
             mix_values,
@@ -10,6 +11,9 @@ use {
         perlin,
         BiomeStats,
         BiomeType,
+        KoppenClass,
+        ResourceType,
+        WorldValidationError,
     },
     bevy::{
         log::info,
@@ -18,9 +22,11 @@ use {
         utils::{default, HashMap},
     },
     crossbeam_channel::Sender,
-    rand::{rngs::StdRng, Rng, SeedableRng},
+    rand::{Rng, SeedableRng},
+    rand_chacha::ChaCha12Rng,
     serde::{Deserialize, Serialize},
     std::{
+        collections::VecDeque,
         error::Error,
         f32::consts::{PI, TAU},
         fmt::{Debug, Display},
@@ -30,11 +36,13 @@ use {
 #[derive(Debug, Clone, Copy)]
 pub enum WorldGenError {
     CartesianError(CartesianError),
+    InvalidWorld(WorldValidationError),
 }
 impl Error for WorldGenError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match *self {
             WorldGenError::CartesianError(ref e) => Some(e),
+            WorldGenError::InvalidWorld(ref e) => Some(e),
         }
     }
 
@@ -50,6 +58,9 @@ impl Display for WorldGenError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             WorldGenError::CartesianError(err) => Display::fmt(err, f),
+            WorldGenError::InvalidWorld(err) => {
+                f.write_fmt(format_args!("Generated world is invalid - {err}"))
+            },
         }
     }
 }
@@ -66,7 +77,7 @@ pub enum CompassDirection {
     NorthWest,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct World {
     pub width:  u32,
     pub height: u32,
@@ -87,9 +98,14 @@ pub struct World {
     pub max_temperature:   f32,
     #[serde(skip)]
     pub min_temperature:   f32,
+    /// Same generator as `StdRng`, whose state can't be saved. Saved after
+    /// the rest of the world, see `saving::runtime_state`.
     #[serde(skip)]
-    pub rng:               StdRng,
+    pub rng:               ChaCha12Rng,
     pub iteration:         usize,
+    /// Divides the chance of resource deposits being placed. Higher values
+    /// make resources rarer.
+    pub resource_scarcity: f32,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -105,6 +121,11 @@ pub struct TerrainCell {
     pub local_iteration: usize,
 
     pub biome_presences: Vec<(BiomeType, f32)>,
+
+    pub soil_fertility: f32,
+    pub arability:      f32,
+
+    pub resources: Vec<(ResourceType, f32)>,
 }
 
 impl TerrainCell {
@@ -124,27 +145,59 @@ impl TerrainCell {
 
 impl World {
     pub const ALTITUDE_SPAN: f32 = World::MAX_ALTITUDE - World::MIN_ALTITUDE;
+    /// Added to the arability of cells near the coast, as a fraction of it
+    pub const COASTAL_PLAIN_BONUS: f32 = 0.15;
+    /// Cells this close to the coast get alluvial and coastal plains bonuses
+    pub const COASTAL_PLAIN_DISTANCE: u32 = 3;
     pub const CONTINENT_MAX_SIZE_FACTOR: f32 = 8.7;
     pub const CONTINENT_MIN_SIZE_FACTOR: f32 = 5.7;
+    pub const DEFAULT_RESOURCE_SCARCITY: f32 = 1.0;
     pub const MAX_ALTITUDE: f32 = 15000.0;
+    /// Cells at least this steep can't be farmed
+    pub const MAX_ARABLE_SLOPE: f32 = 300.0;
+    pub const MAX_COAST_DISTANCE: u32 = u32::MAX;
+    pub const MAX_DEPTH: f32 = -World::MIN_ALTITUDE;
     pub const MAX_RAINFALL: f32 = 13000.0;
+    pub const MAX_SLOPE: f32 = World::ALTITUDE_SPAN;
     pub const MAX_TEMPERATURE: f32 = 30.0;
     pub const MIN_ALTITUDE: f32 = -15000.0;
+    pub const MIN_COAST_DISTANCE: u32 = 0;
+    pub const MIN_DEPTH: f32 = 0.0;
     pub const MIN_RAINFALL: f32 = 0.0;
+    pub const MIN_SLOPE: f32 = 0.0;
     pub const MIN_TEMPERATURE: f32 = -35.0;
     pub const NUM_CONTINENTS: u8 = 12;
     pub const RAINFALL_DRYNESS_FACTOR: f32 = 0.005;
     pub const RAINFALL_DRYNESS_OFFSET: f32 = World::RAINFALL_DRYNESS_FACTOR * World::MAX_RAINFALL;
     pub const RAINFALL_SPAN: f32 = World::MAX_RAINFALL - World::MIN_RAINFALL;
+    /// Stream of the resource placement RNG, so placing resources doesn't
+    /// consume values of the main world RNG
+    pub const RESOURCE_RNG_STREAM: u64 = 0x7265_736f_7572_6365;
     pub const TEMPERATURE_ALTITUDE_FACTOR: f32 = 2.05;
     pub const TEMPERATURE_SPAN: f32 = World::MAX_TEMPERATURE - World::MIN_TEMPERATURE;
 
     pub fn new(width: u32, height: u32, seed: u32) -> World {
+        World::with_terrain(
+            width,
+            height,
+            seed,
+            vec![vec![default(); width.try_into().unwrap()]; height.try_into().unwrap()],
+        )
+    }
+
+    /// A world around existing terrain, which isn't checked against the
+    /// world's size, see [`World::validate`]
+    pub(crate) fn with_terrain(
+        width: u32,
+        height: u32,
+        seed: u32,
+        terrain: Vec<Vec<TerrainCell>>,
+    ) -> World {
         World {
             width,
             height,
             seed,
-            terrain: vec![vec![default(); width.try_into().unwrap()]; height.try_into().unwrap()],
+            terrain,
             continent_offsets: [default(); World::NUM_CONTINENTS as usize],
             continent_sizes: [default(); World::NUM_CONTINENTS as usize],
             max_altitude: World::MIN_ALTITUDE,
@@ -153,8 +206,9 @@ impl World {
             min_rainfall: World::MAX_RAINFALL,
             max_temperature: World::MIN_TEMPERATURE,
             min_temperature: World::MAX_TEMPERATURE,
-            rng: StdRng::seed_from_u64(seed as u64),
+            rng: ChaCha12Rng::seed_from_u64(seed as u64),
             iteration: 0,
+            resource_scarcity: World::DEFAULT_RESOURCE_SCARCITY,
         }
     }
 
@@ -172,8 +226,9 @@ impl World {
             min_rainfall: World::MAX_RAINFALL,
             max_temperature: World::MIN_TEMPERATURE,
             min_temperature: World::MAX_TEMPERATURE,
-            rng: StdRng::seed_from_u64(seed as u64),
+            rng: ChaCha12Rng::seed_from_u64(seed as u64),
             iteration: 0,
+            resource_scarcity: World::DEFAULT_RESOURCE_SCARCITY,
         }
     }
 
@@ -181,10 +236,52 @@ impl World {
         &mut self,
         progress_sender: &Sender<(f32, String)>,
     ) -> Result<(), WorldGenError> {
+        World::validate_resource_scarcity(self.resource_scarcity)
+            .map_err(WorldGenError::InvalidWorld)?;
         send_progress(progress_sender, 0.0, "Generating altitude");
         if let Err(err) = self.generate_altitude(progress_sender) {
             return Err(WorldGenError::CartesianError(err));
         }
+        self.generate_from_altitude(progress_sender)
+    }
+
+    /// Generates a world on the altitudes of a heightmap instead of
+    /// generating them, resampling the heightmap to the world's size if
+    /// needed
+    pub fn generate_from_heightmap(
+        &mut self,
+        heightmap: &Heightmap,
+        options: &HeightmapOptions,
+        progress_sender: &Sender<(f32, String)>,
+    ) -> Result<(), WorldGenError> {
+        World::validate_resource_scarcity(self.resource_scarcity)
+            .map_err(WorldGenError::InvalidWorld)?;
+        send_progress(progress_sender, 0.0, "Importing altitude");
+        info!("Importing altitude");
+        for y in 0..self.terrain.len() {
+            for x in 0..self.terrain[y].len() {
+                let sample = heightmap.world_sample(x, y, self.width, self.height);
+                let altitude = options.altitude(sample);
+
+                let cell = &mut self.terrain[y][x];
+                cell.altitude = altitude;
+                cell.x = x;
+                cell.y = y;
+
+                self.max_altitude = f32::max(self.max_altitude, altitude);
+                self.min_altitude = f32::min(self.min_altitude, altitude);
+            }
+        }
+        info!("Done importing altitude");
+
+        self.generate_from_altitude(progress_sender)
+    }
+
+    /// Every generation step that follows the altitude
+    fn generate_from_altitude(
+        &mut self,
+        progress_sender: &Sender<(f32, String)>,
+    ) -> Result<(), WorldGenError> {
         send_progress(progress_sender, 0.0, "Generating rainfall");
         if let Err(err) = self.generate_rainfall(progress_sender) {
             return Err(WorldGenError::CartesianError(err));
@@ -194,10 +291,18 @@ impl World {
             return Err(WorldGenError::CartesianError(err));
         }
 
+        let coast_distances = self.coast_distances();
+
         send_progress(progress_sender, 0.0, "Generating biomes");
-        self.generate_biomes(progress_sender);
+        self.generate_biomes(progress_sender, &coast_distances);
 
-        Ok(())
+        send_progress(progress_sender, 0.0, "Generating arability");
+        self.generate_arability(progress_sender, &coast_distances);
+
+        send_progress(progress_sender, 0.0, "Generating resources");
+        self.generate_resources(progress_sender);
+
+        self.validate().map_err(WorldGenError::InvalidWorld)
     }
 
     fn generate_continents(&mut self, progress_sender: &Sender<(f32, String)>) {
@@ -448,7 +553,7 @@ impl World {
         Ok(())
     }
 
-    fn random_offset_vector(rng: &mut StdRng) -> Vec3A {
+    fn random_offset_vector(rng: &mut ChaCha12Rng) -> Vec3A {
         random_point_in_sphere(rng, 1000.0)
     }
 
@@ -664,44 +769,78 @@ impl World {
         )
     }
 
-    fn generate_biomes(&mut self, progress_sender: &Sender<(f32, String)>) {
+    /// Replaces the biomes of a single cell with the ones its terrain supports
+    pub(crate) fn generate_cell_biomes(&mut self, x: usize, y: usize, coast_distance: u32) {
+        let cell = &self.terrain[y][x];
+        let slope = f32::abs(self.get_slant(cell));
+
+        let mut total_presence = 0.0;
+
+        let mut biome_presences = vec![];
+        for biome_type in BiomeType::iterator() {
+            let presence = self.biome_presence(cell, biome_type.stats(), slope, coast_distance);
+
+            if presence <= 0.0 {
+                continue;
+            }
+
+            biome_presences.push((*biome_type, presence));
+            total_presence += presence;
+        }
+        self.terrain[y][x].biome_presences = biome_presences
+            .iter()
+            .map(|(biome_type, presence)| (*biome_type, presence / total_presence))
+            .collect();
+    }
+
+    pub(crate) fn generate_biomes(
+        &mut self,
+        progress_sender: &Sender<(f32, String)>,
+        coast_distances: &[Vec<u32>],
+    ) {
         info!("Generating biomes");
         let height = self.terrain.len();
-        for y in 0..height {
-            let width = self.terrain[y].len();
+        for (y, row_distances) in coast_distances.iter().enumerate() {
+            let width = row_distances.len();
             let size = height * width;
-            for x in 0..width {
+            for (x, &coast_distance) in row_distances.iter().enumerate() {
                 let index = y * width + x;
                 send_progress(
                     progress_sender,
                     index as f32 / size as f32,
                     format!("Generating biomes: {index}/{size}"),
                 );
-                let cell = &self.terrain[y][x];
-
-                let mut total_presence = 0.0;
-
-                let mut biome_presences = vec![];
-                for biome_type in BiomeType::iterator() {
-                    let presence = self.biome_presence(cell, &biome_type.into());
-
-                    if presence <= 0.0 {
-                        continue;
-                    }
-
-                    biome_presences.push((*biome_type, presence));
-                    total_presence += presence;
-                }
-                self.terrain[y][x].biome_presences = biome_presences
-                    .iter()
-                    .map(|(biome_type, presence)| (*biome_type, presence / total_presence))
-                    .collect();
+                self.generate_cell_biomes(x, y, coast_distance);
             }
         }
         info!("Done generating biomes");
     }
 
-    fn biome_presence(&self, cell: &TerrainCell, biome: &BiomeStats) -> f32 {
+    /// How strongly a biome is present in a cell, before the presences of all
+    /// biomes in the cell are normalized. 0 where the biome can't appear.
+    #[must_use]
+    pub fn biome_presence(
+        &self,
+        cell: &TerrainCell,
+        biome: &BiomeStats,
+        slope: f32,
+        coast_distance: u32,
+    ) -> f32 {
+        // Slope, depth and coast distance only limit where a biome can appear,
+        // they don't contribute to how strongly it is present.
+        if !(biome.min_slope..=biome.max_slope).contains(&slope) {
+            return 0.0;
+        }
+
+        let depth = f32::max(0.0, -cell.altitude);
+        if !(biome.min_depth..=biome.max_depth).contains(&depth) {
+            return 0.0;
+        }
+
+        if !(biome.min_coast_distance..=biome.max_coast_distance).contains(&coast_distance) {
+            return 0.0;
+        }
+
         let mut presence = 0.0;
         let altitude_diff = cell.altitude - biome.min_altitude;
         if altitude_diff < 0.0 {
@@ -754,6 +893,184 @@ impl World {
         presence
     }
 
+    pub(crate) fn generate_arability(
+        &mut self,
+        progress_sender: &Sender<(f32, String)>,
+        coast_distances: &[Vec<u32>],
+    ) {
+        info!("Generating arability");
+        let height = self.terrain.len();
+        for (y, row_distances) in coast_distances.iter().enumerate() {
+            let width = row_distances.len();
+            let size = height * width;
+            for (x, &coast_distance) in row_distances.iter().enumerate() {
+                let index = y * width + x;
+                send_progress(
+                    progress_sender,
+                    index as f32 / size as f32,
+                    format!("Generating arability: {index}/{size}"),
+                );
+                let cell = &mut self.terrain[y][x];
+                cell.soil_fertility = cell
+                    .biome_presences
+                    .iter()
+                    .map(|(biome_type, presence)| biome_type.stats().fertility * presence)
+                    .sum();
+
+                self.terrain[y][x].arability = self.arability(&self.terrain[y][x], coast_distance);
+            }
+        }
+        info!("Done generating arability");
+    }
+
+    /// How much of a cell's soil fertility can be farmed, from its climate,
+    /// slope and distance to the coast. Water can't be farmed.
+    #[must_use]
+    pub fn arability(&self, cell: &TerrainCell, coast_distance: u32) -> f32 {
+        if cell.altitude <= 0.0 {
+            return 0.0;
+        }
+
+        let rainfall_factor = f32::clamp((cell.rainfall - 200.0) / 600.0, 0.0, 1.0)
+            * (1.0 - f32::clamp((cell.rainfall - 2500.0) / 5000.0, 0.0, 0.5));
+        let temperature_factor = f32::clamp((cell.temperature + 2.0) / 12.0, 0.0, 1.0);
+        let slope_factor = 1.0
+            - f32::clamp(
+                f32::abs(self.get_slant(cell)) / World::MAX_ARABLE_SLOPE,
+                0.0,
+                1.0,
+            );
+        let proximity_bonus = if coast_distance <= World::COASTAL_PLAIN_DISTANCE {
+            World::COASTAL_PLAIN_BONUS
+        } else {
+            0.0
+        };
+
+        f32::clamp(
+            cell.soil_fertility
+                * rainfall_factor
+                * temperature_factor
+                * slope_factor
+                * (1.0 + proximity_bonus),
+            0.0,
+            1.0,
+        )
+    }
+
+    pub(crate) fn generate_resources(&mut self, progress_sender: &Sender<(f32, String)>) {
+        info!("Generating resources");
+        let mut rng = ChaCha12Rng::seed_from_u64(u64::from(self.seed));
+        rng.set_stream(World::RESOURCE_RNG_STREAM);
+
+        let height = self.terrain.len();
+        for y in 0..height {
+            let width = self.terrain[y].len();
+            let size = height * width;
+            for x in 0..width {
+                let index = y * width + x;
+                send_progress(
+                    progress_sender,
+                    index as f32 / size as f32,
+                    format!("Generating resources: {index}/{size}"),
+                );
+                let cell = &self.terrain[y][x];
+                let slope = f32::abs(self.get_slant(cell));
+
+                let mut resources = vec![];
+                for resource_type in ResourceType::iterator() {
+                    let resource = resource_type.stats();
+
+                    if !(resource.min_altitude..=resource.max_altitude).contains(&cell.altitude)
+                        || !(resource.min_slope..=resource.max_slope).contains(&slope)
+                    {
+                        continue;
+                    }
+
+                    let suitability: f32 = if resource.biomes.is_empty() {
+                        1.0
+                    } else {
+                        cell.biome_presences
+                            .iter()
+                            .filter(|(biome_type, _)| resource.biomes.contains(biome_type))
+                            .map(|(_, presence)| presence)
+                            .sum()
+                    };
+                    if suitability <= 0.0 {
+                        continue;
+                    }
+
+                    let chance = resource.frequency * suitability / self.resource_scarcity;
+                    if rng.gen_range(0.0..1.0) < chance {
+                        resources.push((*resource_type, suitability * rng.gen_range(0.25..1.0)));
+                    }
+                }
+
+                self.terrain[y][x].resources = resources;
+            }
+        }
+        info!("Done generating resources");
+    }
+
+    /// Distance in cells from every cell to the nearest coastline, on either
+    /// the land or the water side. Cells directly at the coast have a distance
+    /// of 0. If the world has no coastline, all distances are
+    /// [`World::MAX_COAST_DISTANCE`].
+    #[must_use]
+    pub fn coast_distances(&self) -> Vec<Vec<u32>> {
+        let mut distances: Vec<Vec<u32>> = self
+            .terrain
+            .iter()
+            .map(|row| vec![World::MAX_COAST_DISTANCE; row.len()])
+            .collect();
+        let mut queue = VecDeque::new();
+
+        for cell in self.terrain.iter().flatten() {
+            if self.is_cell_coastline(cell) || self.is_cell_near_coastline(cell) {
+                distances[cell.y][cell.x] = 0;
+                queue.push_back((cell.x, cell.y));
+            }
+        }
+
+        while let Some((x, y)) = queue.pop_front() {
+            let distance = distances[y][x] + 1;
+            for neighbor in self.cell_neighbors(x, y).values() {
+                if distances[neighbor.y][neighbor.x] > distance {
+                    distances[neighbor.y][neighbor.x] = distance;
+                    queue.push_back((neighbor.x, neighbor.y));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Latitude of a terrain row in radians, from -PI/2 at row 0 to PI/2 at
+    /// the last row
+    #[must_use]
+    pub fn latitude(&self, y: usize) -> f32 {
+        (y as f32 / self.height as f32) * PI - PI / 2.0
+    }
+
+    /// Köppen-Geiger climate class of a land cell, or `None` for water.
+    ///
+    /// The class isn't stored in the [`TerrainCell`], as it only depends on
+    /// the cell's temperature, rainfall and latitude. Classifying takes a few
+    /// comparisons, so storing the class would only make saves larger, and
+    /// deriving it lets changes to [`KoppenClass::classify`] apply to existing
+    /// saves without migrating them.
+    #[must_use]
+    pub fn koppen_class(&self, cell: &TerrainCell) -> Option<KoppenClass> {
+        if cell.altitude <= 0.0 {
+            return None;
+        }
+
+        Some(KoppenClass::classify(
+            cell.temperature,
+            cell.rainfall,
+            self.latitude(cell.y),
+        ))
+    }
+
     #[must_use]
     pub fn cell_neighbors(&self, x: usize, y: usize) -> HashMap<CompassDirection, &TerrainCell> {
         let mut neighbors = HashMap::new();
@@ -933,7 +1250,7 @@ impl World {
     }
 }
 
-fn send_progress<T: Into<String>>(
+pub(crate) fn send_progress<T: Into<String>>(
     progress_sender: &Sender<(f32, String)>,
     progress: f32,
     progress_text: T,
@@ -1,7 +1,40 @@
 use {
-    crate::{World, WorldGenError},
+    crate::{
+        export::{
+            heightmap,
+            map_image,
+            metadata::LayerMetadata,
+            raster,
+            DataLayer,
+            ExportError,
+            ExportMetadata,
+            HeightmapFormat,
+            MapImageOptions,
+        },
+        import::{plnt, ImportError, PlntImportReport},
+        saving::{
+            atomic,
+            checksum::{self, ChecksumWriter, CHECKSUM_LENGTH, CHECKSUM_SAVE_VERSION},
+            compression::{self, DeflateWriter, InflateReader},
+            json,
+            metadata::{SaveMetadata, WorldInfo, METADATA_SAVE_VERSION},
+            migration,
+            recipe::GENERATOR_VERSION,
+            stream::{self, STREAMED_SAVE_VERSION},
+            tabular,
+            SaveCompression,
+            SaveHeader,
+            SaveOptions,
+            WorldRecipe,
+            LEGACY_SAVE_VERSION,
+        },
+        world::send_progress,
+        World,
+        WorldGenError,
+        WorldValidationError,
+    },
     bevy::{
-        log::warn,
+        log::{info, warn},
         prelude::Resource,
         tasks::{AsyncComputeTaskPool, Task},
         utils::default,
@@ -11,8 +44,8 @@ use {
     std::{
         error::Error,
         fmt::Display,
-        fs::File,
-        io::{self, Read, Write},
+        fs::{self, File},
+        io::{self, BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write},
         path::Path,
     },
 };
@@ -21,12 +54,26 @@ use {
 pub enum LoadError {
     MissingSave(io::Error),
     InvalidSave(postcard::Error),
+    Corrupted,
+    UnsupportedVersion(u16),
+    UnsupportedCompression(u8),
+    FailedToDecompress,
+    InvalidRecipe(ron::error::SpannedError),
+    InvalidJson(serde_json::Error),
+    InvalidWorld(WorldValidationError),
 }
 impl Error for LoadError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             LoadError::MissingSave(error) => Some(error),
             LoadError::InvalidSave(error) => Some(error),
+            LoadError::Corrupted => None,
+            LoadError::UnsupportedVersion(_) => None,
+            LoadError::UnsupportedCompression(_) => None,
+            LoadError::FailedToDecompress => None,
+            LoadError::InvalidRecipe(error) => Some(error),
+            LoadError::InvalidJson(error) => Some(error),
+            LoadError::InvalidWorld(error) => Some(error),
         }
     }
 
@@ -46,6 +93,25 @@ impl Display for LoadError {
                 "Loaded file is not a valid save - {}",
                 err.to_string()
             )),
+            LoadError::Corrupted => f.write_str(
+                "Save is corrupted or incomplete, its contents don't match its checksum",
+            ),
+            LoadError::UnsupportedVersion(version) => f.write_fmt(format_args!(
+                "Save format version {version} is not supported by this version of the game"
+            )),
+            LoadError::UnsupportedCompression(compression) => f.write_fmt(format_args!(
+                "Save is compressed with an unknown method ({compression})"
+            )),
+            LoadError::FailedToDecompress => f.write_str("Failed to decompress save"),
+            LoadError::InvalidRecipe(err) => f.write_fmt(format_args!(
+                "Loaded file is not a valid world recipe - {err}"
+            )),
+            LoadError::InvalidJson(err) => f.write_fmt(format_args!(
+                "Loaded file is not a valid JSON world - {err}"
+            )),
+            LoadError::InvalidWorld(err) => {
+                f.write_fmt(format_args!("Loaded world is inconsistent - {err}"))
+            },
         }
     }
 }
@@ -53,14 +119,22 @@ impl Display for LoadError {
 #[derive(Debug)]
 pub enum SaveError {
     MissingWorld,
+    NotGenerated(WorldOrigin),
     SerializationError(postcard::Error),
+    RecipeSerializationError(ron::Error),
+    JsonSerializationError(serde_json::Error),
+    CsvSerializationError(csv::Error),
     FailedToWrite(io::Error),
 }
 impl Error for SaveError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             SaveError::MissingWorld => None,
+            SaveError::NotGenerated(_) => None,
             SaveError::SerializationError(error) => Some(error),
+            SaveError::RecipeSerializationError(error) => Some(error),
+            SaveError::JsonSerializationError(error) => Some(error),
+            SaveError::CsvSerializationError(error) => Some(error),
             SaveError::FailedToWrite(err) => Some(err),
         }
     }
@@ -73,19 +147,109 @@ impl Error for SaveError {
         self.source()
     }
 }
+impl From<io::Error> for SaveError {
+    fn from(error: io::Error) -> Self {
+        SaveError::FailedToWrite(error)
+    }
+}
 impl Display for SaveError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             SaveError::MissingWorld => f.write_str("No world to save found."),
+            SaveError::NotGenerated(origin) => f.write_fmt(format_args!(
+                "Only worlds generated from a seed can be saved as a recipe, this one was {}.",
+                origin.description()
+            )),
             SaveError::SerializationError(_) => f.write_str("Failed to serialize world."),
+            SaveError::RecipeSerializationError(_) => {
+                f.write_str("Failed to serialize world recipe.")
+            },
+            SaveError::JsonSerializationError(_) => {
+                f.write_str("Failed to serialize world to JSON.")
+            },
+            SaveError::CsvSerializationError(_) => f.write_str("Failed to write terrain table."),
             SaveError::FailedToWrite(_) => f.write_str("Failed to write save file."),
         }
     }
 }
 
-#[derive(Debug, Default, Resource)]
+#[derive(Debug, Clone, Copy)]
+pub struct SaveSizeReport {
+    pub options:           SaveOptions,
+    /// Size of the serialized world before compression, in bytes
+    pub uncompressed_size: usize,
+    /// Size of the written file including its header, in bytes
+    pub file_size:         usize,
+}
+impl Display for SaveSizeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        const KIB: f32 = 1024.0;
+        f.write_fmt(format_args!("{:.1} KiB", self.file_size as f32 / KIB))?;
+        if self.options.compression != SaveCompression::None {
+            f.write_fmt(format_args!(
+                ", {:.1} KiB uncompressed ({:.0}%)",
+                self.uncompressed_size as f32 / KIB,
+                100.0 * self.file_size as f32 / self.uncompressed_size as f32
+            ))?;
+        }
+        if self.options.quantize {
+            f.write_str(", quantized")?;
+        }
+        Ok(())
+    }
+}
+
+/// How the current world was made, which decides whether a [`WorldRecipe`]
+/// can regenerate it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorldOrigin {
+    /// Generated from a seed, either a random one or a recipe's
+    #[default]
+    Generated,
+    /// Generated on the altitudes of a heightmap
+    Heightmap,
+    /// Imported from a save of the original Worlds History Sim
+    Imported,
+    /// Read from a save or a JSON world, neither of which records how the
+    /// world was made
+    Loaded,
+}
+impl WorldOrigin {
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            WorldOrigin::Generated => "generated from a seed",
+            WorldOrigin::Heightmap => "generated from a heightmap",
+            WorldOrigin::Imported => "imported",
+            WorldOrigin::Loaded => "loaded from a file",
+        }
+    }
+}
+
+/// Settings for worlds generated from a random seed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NewWorldOptions {
+    pub resource_scarcity: f32,
+}
+
+impl Default for NewWorldOptions {
+    fn default() -> Self {
+        Self {
+            resource_scarcity: World::DEFAULT_RESOURCE_SCARCITY,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Resource)]
 pub struct WorldManager {
-    world: Option<World>,
+    world:             Option<World>,
+    /// Bumped whenever the world may have changed, see
+    /// [`WorldManager::world_revision`]
+    world_revision:    u64,
+    info:              WorldInfo,
+    origin:            WorldOrigin,
+    save_options:      SaveOptions,
+    new_world_options: NewWorldOptions,
 }
 
 impl WorldManager {
@@ -97,44 +261,378 @@ impl WorldManager {
         default()
     }
 
-    pub fn save_world<P: AsRef<Path>>(&self, path: P) -> Result<(), SaveError> {
+    pub fn save_world<P: AsRef<Path>>(&self, path: P) -> Result<SaveSizeReport, SaveError> {
         let Some(world) = self.get_world() else {
             warn!("No world to save");
             return Err(SaveError::MissingWorld);
         };
 
-        let serialized = match postcard::to_stdvec(world) {
-            Ok(serialized) => serialized,
+        let options = self.save_options;
+        let metadata = match SaveMetadata::new(world, &self.info).to_block() {
+            Ok(metadata) => metadata,
             Err(err) => {
                 return Err(SaveError::SerializationError(err));
             },
         };
+        let header = SaveHeader::new(options).to_bytes();
 
-        match File::create(path).unwrap().write_all(serialized.as_slice()) {
-            Ok(_) => Ok(()),
-            Err(err) => Err(SaveError::FailedToWrite(err)),
-        }
+        let report = atomic::write_atomically_with(path, |file| {
+            let mut writer = ChecksumWriter::new(BufWriter::new(file));
+            writer.write_all(&header)?;
+            writer.write_all(&metadata)?;
+            let uncompressed_size = match options.compression {
+                SaveCompression::None => stream::write_body(world, options.quantize, &mut writer)?,
+                SaveCompression::Deflate => {
+                    let mut encoder =
+                        DeflateWriter::new(&mut writer, SaveCompression::DEFLATE_LEVEL);
+                    let uncompressed_size =
+                        stream::write_body(world, options.quantize, &mut encoder)?;
+                    _ = encoder.finish()?;
+                    uncompressed_size
+                },
+            };
+            let (mut writer, file_size) = writer.finish()?;
+            writer.flush()?;
+
+            Ok::<_, SaveError>(SaveSizeReport {
+                options,
+                uncompressed_size,
+                file_size,
+            })
+        })?;
+        info!("Saved world: {report}");
+        Ok(report)
     }
 
     pub fn load_world<P: AsRef<Path>>(&mut self, path: P) -> Result<(), LoadError> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(err) => {
+                return Err(LoadError::MissingSave(err));
+            },
+        };
+
+        self.load_world_from_reader(BufReader::new(file))
+    }
+
+    /// Loads a save from its contents, see [`WorldManager::load_world`]
+    pub fn load_world_from_bytes(&mut self, bytes: &[u8]) -> Result<(), LoadError> {
+        self.load_world_from_reader(Cursor::new(bytes))
+    }
+
+    /// Loads a save from a reader. Saves since version 6 are decoded a few
+    /// rows at a time, older saves are read into memory as a whole first.
+    pub fn load_world_from_reader<R: Read + Seek>(&mut self, reader: R) -> Result<(), LoadError> {
+        let (progress_sender, _) = crossbeam_channel::bounded(0);
+        let (world, info) = WorldManager::read_save(reader, &progress_sender)?;
+        self.set_loaded_world(world, info);
+        Ok(())
+    }
+
+    /// Reads and validates a save without replacing the current world, so
+    /// that it can be decoded away from the main thread. See
+    /// [`WorldManager::load_world_from_reader`].
+    pub fn read_save<R: Read + Seek>(
+        mut reader: R,
+        progress_sender: &Sender<(f32, String)>,
+    ) -> Result<(World, WorldInfo), LoadError> {
+        send_progress(progress_sender, 0.0, "Reading save");
+        let mut start = Vec::with_capacity(SaveHeader::LENGTH);
+        if let Err(err) = reader
+            .by_ref()
+            .take(SaveHeader::LENGTH as u64)
+            .read_to_end(&mut start)
+        {
+            return Err(LoadError::MissingSave(err));
+        }
+        let header = match SaveHeader::from_bytes(&start)? {
+            Some(header) if header.version >= STREAMED_SAVE_VERSION => header,
+            _ => {
+                let mut buf = vec![];
+                if let Err(err) = reader.rewind().and_then(|()| reader.read_to_end(&mut buf)) {
+                    return Err(LoadError::MissingSave(err));
+                }
+                return WorldManager::read_buffered(&buf);
+            },
+        };
+
+        send_progress(progress_sender, 0.0, "Verifying save");
+        let length = reader
+            .seek(SeekFrom::End(0))
+            .and_then(|length| reader.rewind().map(|()| length))
+            .map_err(LoadError::MissingSave)?;
+        checksum::verify_checksum_streaming(&mut reader, length)?;
+        reader
+            .seek(SeekFrom::Start(header.length() as u64))
+            .map_err(LoadError::MissingSave)?;
+        let body_length = length.saturating_sub((header.length() + CHECKSUM_LENGTH) as u64);
+        let mut body = reader.take(body_length);
+
+        let metadata: SaveMetadata = stream::read_record(&mut body)?;
+        let quantized = header.options.quantize;
+        let world = match header.options.compression {
+            SaveCompression::None => stream::read_body(quantized, &mut body, progress_sender)?,
+            SaveCompression::Deflate => stream::read_body(
+                quantized,
+                &mut InflateReader::new(&mut body),
+                progress_sender,
+            )?,
+        };
+
+        send_progress(progress_sender, 1.0, "Checking world");
+        world.validate().map_err(LoadError::InvalidWorld)?;
+        Ok((world, metadata.info))
+    }
+
+    /// Reads saves written before version 6, see [`WorldManager::read_save`]
+    fn read_buffered(buf: &[u8]) -> Result<(World, WorldInfo), LoadError> {
+        let (header, body) = match SaveHeader::from_bytes(buf)? {
+            Some(header) if header.version >= CHECKSUM_SAVE_VERSION => {
+                let content = checksum::verify_checksum(buf)?;
+                (header, &content[header.length()..])
+            },
+            Some(header) => (header, &buf[header.length()..]),
+            None => (
+                SaveHeader {
+                    version: LEGACY_SAVE_VERSION,
+                    options: SaveOptions {
+                        compression: SaveCompression::None,
+                        quantize:    false,
+                    },
+                },
+                buf,
+            ),
+        };
+        let (info, body) = if header.version >= METADATA_SAVE_VERSION {
+            let (metadata, body) = SaveMetadata::from_block(body)?;
+            (metadata.info, body)
+        } else {
+            (WorldInfo::default(), body)
+        };
+        let body = compression::decompress(body, header.options.compression)?;
+
+        let world = migration::deserialize_world(header, &body)?;
+        world.validate().map_err(LoadError::InvalidWorld)?;
+        Ok((world, info))
+    }
+
+    /// Reads what a save describes itself with, without loading its terrain.
+    /// Returns `None` for saves written before version 4, which don't have
+    /// metadata and would have to be loaded in full to describe them. The
+    /// checksum isn't verified, a corrupted save is only noticed when loading
+    /// it.
+    pub fn peek_save<P: AsRef<Path>>(path: P) -> Result<Option<SaveMetadata>, LoadError> {
+        let path = path.as_ref();
         let mut file = match File::open(path) {
             Ok(file) => file,
             Err(err) => {
                 return Err(LoadError::MissingSave(err));
             },
         };
-        let mut buf = vec![];
-        if let Err(err) = file.read_to_end(&mut buf) {
+        // Enough for the header and the metadata block's length
+        let mut start = vec![0; SaveHeader::LENGTH + 4];
+        if let Err(err) = file.read_exact(&mut start) {
             return Err(LoadError::MissingSave(err));
+        }
+
+        match SaveHeader::from_bytes(&start)? {
+            Some(header) if header.version >= METADATA_SAVE_VERSION => {
+                let block_start = &start[header.length()..];
+                let rest_length = SaveMetadata::block_length(block_start)? - block_start.len();
+                // Grows with what is actually read, so a corrupted length can't
+                // make it allocate more than the save holds
+                let mut block = block_start.to_vec();
+                if let Err(err) = file.take(rest_length as u64).read_to_end(&mut block) {
+                    return Err(LoadError::MissingSave(err));
+                }
+                SaveMetadata::from_block(&block).map(|(metadata, _)| Some(metadata))
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Writes a recipe that regenerates the current world, see [`WorldRecipe`].
+    /// Fails for worlds that weren't generated from a seed, which a recipe
+    /// would regenerate as a different world.
+    pub fn save_recipe<P: AsRef<Path>>(&self, path: P) -> Result<(), SaveError> {
+        let Some(world) = self.get_world() else {
+            warn!("No world to save");
+            return Err(SaveError::MissingWorld);
+        };
+        if !self.can_save_recipe() {
+            return Err(SaveError::NotGenerated(self.origin));
+        }
+
+        let recipe = match WorldRecipe::new(world).to_ron() {
+            Ok(recipe) => recipe,
+            Err(err) => {
+                return Err(SaveError::RecipeSerializationError(err));
+            },
+        };
+
+        match atomic::write_atomically(path, &[recipe.as_bytes()]) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(SaveError::FailedToWrite(err)),
+        }
+    }
+
+    /// Reads a recipe, which can then be generated with
+    /// [`WorldManager::new_world_from_recipe_async`]
+    pub fn load_recipe<P: AsRef<Path>>(&self, path: P) -> Result<WorldRecipe, LoadError> {
+        let recipe = match fs::read_to_string(path) {
+            Ok(recipe) => recipe,
+            Err(err) => {
+                return Err(LoadError::MissingSave(err));
+            },
+        };
+
+        let recipe = WorldRecipe::from_ron(&recipe).map_err(LoadError::InvalidRecipe)?;
+        if !recipe.matches_generator() {
+            warn!(
+                "Recipe was written by generator version {}, this is version {}. The world may \
+                 differ from the original.",
+                recipe.generator_version, GENERATOR_VERSION
+            );
+        }
+        Ok(recipe)
+    }
+
+    /// Writes the world as hand-editable JSON, see [`json::to_json`]
+    pub fn save_json<P: AsRef<Path>>(&self, path: P) -> Result<(), SaveError> {
+        let Some(world) = self.get_world() else {
+            warn!("No world to save");
+            return Err(SaveError::MissingWorld);
         };
 
-        match postcard::from_bytes(buf.as_slice()) {
-            Ok(world) => {
-                self.world = Some(world);
-                Ok(())
+        let serialized = json::to_json(world).map_err(SaveError::JsonSerializationError)?;
+        match atomic::write_atomically(path, &[serialized.as_bytes()]) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(SaveError::FailedToWrite(err)),
+        }
+    }
+
+    pub fn load_json<P: AsRef<Path>>(&mut self, path: P) -> Result<(), LoadError> {
+        let serialized = match fs::read_to_string(path) {
+            Ok(serialized) => serialized,
+            Err(err) => {
+                return Err(LoadError::MissingSave(err));
             },
-            Err(err) => Err(LoadError::InvalidSave(err)),
+        };
+
+        let world = json::from_json(&serialized).map_err(LoadError::InvalidJson)?;
+        world.validate().map_err(LoadError::InvalidWorld)?;
+        self.set_world(world, WorldOrigin::Loaded);
+        Ok(())
+    }
+
+    /// Writes a table of every cell, see [`tabular::write_csv`]
+    pub fn save_csv<P: AsRef<Path>>(&self, path: P) -> Result<(), SaveError> {
+        let Some(world) = self.get_world() else {
+            warn!("No world to save");
+            return Err(SaveError::MissingWorld);
+        };
+
+        let mut table = vec![];
+        tabular::write_csv(world, &mut table).map_err(SaveError::CsvSerializationError)?;
+        atomic::write_atomically(path, &[&table]).map_err(SaveError::FailedToWrite)
+    }
+
+    /// Renders the current world's map to a PNG image, see [`MapImageOptions`]
+    pub fn export_map_png<P: AsRef<Path>>(
+        &self,
+        path: P,
+        options: &MapImageOptions,
+    ) -> Result<(), ExportError> {
+        let Some(world) = self.get_world() else {
+            warn!("No world to export");
+            return Err(ExportError::MissingWorld);
+        };
+
+        map_image::export_map_png(world, options, &path)?;
+        info!("Exported map to {}", path.as_ref().display());
+        Ok(())
+    }
+
+    /// Exports the altitude as a 16-bit heightmap, rainfall and temperature as
+    /// NumPy arrays, and a sidecar [`ExportMetadata`] file describing them.
+    /// `base_path` is extended per file, e.g. `world` becomes
+    /// `world.altitude.png`, `world.rainfall.npy` and so on.
+    pub fn export_data<P: AsRef<Path>>(
+        &self,
+        base_path: P,
+        heightmap_format: HeightmapFormat,
+    ) -> Result<ExportMetadata, ExportError> {
+        let Some(world) = self.get_world() else {
+            warn!("No world to export");
+            return Err(ExportError::MissingWorld);
+        };
+
+        let base_path = base_path.as_ref();
+        let base_name = base_path.file_name().map_or_else(
+            || String::from("world"),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let file_name = |suffix: &str, extension: &str| format!("{base_name}.{suffix}.{extension}");
+        // Every file is written atomically, so an interrupted export doesn't
+        // leave truncated layers behind
+        let path = |file_name: &str| base_path.with_file_name(file_name);
+
+        let mut metadata = ExportMetadata::new(world);
+
+        let heightmap_name = file_name(
+            DataLayer::Altitude.file_suffix(),
+            heightmap_format.extension(),
+        );
+        atomic::write_atomically_with(path(&heightmap_name), |file| {
+            heightmap::write_heightmap(world, heightmap_format, file)
+        })?;
+        metadata.layers.push(LayerMetadata::new(
+            world,
+            DataLayer::Altitude,
+            heightmap_name,
+            heightmap_format.description(),
+        ));
+
+        for layer in [DataLayer::Rainfall, DataLayer::Temperature] {
+            let name = file_name(layer.file_suffix(), raster::NPY_EXTENSION);
+            atomic::write_atomically_with(path(&name), |file| {
+                raster::write_npy(world, layer, file)
+            })
+            .map_err(ExportError::FailedToWrite)?;
+            metadata.layers.push(LayerMetadata::new(
+                world,
+                layer,
+                name,
+                raster::NPY_DESCRIPTION,
+            ));
         }
+
+        let serialized = metadata
+            .to_ron()
+            .map_err(ExportError::MetadataSerializationError)?;
+        atomic::write_atomically(
+            path(&format!("{base_name}.{}", ExportMetadata::EXTENSION)),
+            &[serialized.as_bytes()],
+        )
+        .map_err(ExportError::FailedToWrite)?;
+
+        info!("Exported world data to {}", base_path.display());
+        Ok(metadata)
+    }
+
+    /// Replaces the current world with one imported from a save of the
+    /// original Worlds History Sim, see [`crate::import::plnt`]
+    pub fn import_plnt<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<PlntImportReport, ImportError> {
+        let xml = fs::read_to_string(path).map_err(ImportError::FailedToRead)?;
+        let (world, report) = plnt::import_plnt(&xml)?;
+        if !report.is_complete() {
+            warn!("Imported world is incomplete:\n{report}");
+        }
+        self.set_world(world, WorldOrigin::Imported);
+        Ok(report)
     }
 
     #[must_use]
@@ -142,22 +640,99 @@ impl WorldManager {
         self.world.as_ref()
     }
 
-    pub fn set_world(&mut self, world: World) {
+    #[must_use]
+    pub fn get_world_mut(&mut self) -> Option<&mut World> {
+        self.world_revision += 1;
+        self.world.as_mut()
+    }
+
+    /// Changes whenever the world is replaced or borrowed mutably, so that
+    /// what is derived from it can be recomputed. Unlike the seed, it tells
+    /// apart different worlds generated from the same seed.
+    #[must_use]
+    pub fn world_revision(&self) -> u64 {
+        self.world_revision
+    }
+
+    /// Replaces the current world with a new one, which starts with empty
+    /// [`WorldInfo`]
+    pub fn set_world(&mut self, world: World, origin: WorldOrigin) {
         self.world = Some(world);
+        self.world_revision += 1;
+        self.info = WorldInfo::default();
+        self.origin = origin;
+    }
+
+    /// Replaces the current world with one read from a save, see
+    /// [`WorldManager::read_save`]
+    pub fn set_loaded_world(&mut self, world: World, info: WorldInfo) {
+        self.world = Some(world);
+        self.world_revision += 1;
+        self.info = info;
+        self.origin = WorldOrigin::Loaded;
+    }
+
+    #[must_use]
+    pub fn origin(&self) -> WorldOrigin {
+        self.origin
+    }
+
+    /// Whether [`WorldManager::save_recipe`] can describe the current world
+    #[must_use]
+    pub fn can_save_recipe(&self) -> bool {
+        self.world.is_some() && self.origin == WorldOrigin::Generated
+    }
+
+    #[must_use]
+    pub fn info(&self) -> &WorldInfo {
+        &self.info
+    }
+
+    #[must_use]
+    pub fn info_mut(&mut self) -> &mut WorldInfo {
+        &mut self.info
+    }
+
+    #[must_use]
+    pub fn save_options(&self) -> SaveOptions {
+        self.save_options
+    }
+
+    pub fn set_save_options(&mut self, save_options: SaveOptions) {
+        self.save_options = save_options;
+    }
+
+    #[must_use]
+    pub fn new_world_options(&self) -> NewWorldOptions {
+        self.new_world_options
+    }
+
+    pub fn set_new_world_options(&mut self, new_world_options: NewWorldOptions) {
+        self.new_world_options = new_world_options;
     }
 
     pub fn new_world_async(
         &mut self,
         seed: Option<u32>,
         progress_sender: Sender<(f32, String)>,
+    ) -> Task<Result<World, WorldGenError>> {
+        let recipe = WorldRecipe {
+            generator_version: String::from(GENERATOR_VERSION),
+            seed:              seed.unwrap_or_else(random),
+            width:             WorldManager::NEW_WORLD_WIDTH,
+            height:            WorldManager::NEW_WORLD_HEIGHT,
+            resource_scarcity: self.new_world_options.resource_scarcity,
+        };
+        self.new_world_from_recipe_async(recipe, progress_sender)
+    }
+
+    pub fn new_world_from_recipe_async(
+        &mut self,
+        recipe: WorldRecipe,
+        progress_sender: Sender<(f32, String)>,
     ) -> Task<Result<World, WorldGenError>> {
         AsyncComputeTaskPool::get().spawn(async move {
-            let seed = seed.unwrap_or_else(random);
-            let mut new_world = World::async_new(
-                WorldManager::NEW_WORLD_WIDTH,
-                WorldManager::NEW_WORLD_HEIGHT,
-                seed,
-            );
+            let mut new_world = recipe.world();
             if let Err(_) = progress_sender.try_send((0.0, String::from("Generating new world...")))
             {
                 // Quietly ignore. It's not critical and logging is slow.
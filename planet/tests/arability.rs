@@ -0,0 +1,57 @@
+use planet::World;
+
+/// A farmable world rising by `rise` metres a row from south to north, so its
+/// middle row has a slope of a third of `rise`
+fn sloped_world(rise: f32) -> World {
+    let mut world = World::new(4, 3, 0);
+    for (y, row) in world.terrain.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            cell.x = x;
+            cell.y = y;
+            cell.altitude = 100.0 + rise * y as f32;
+            cell.rainfall = 1000.0;
+            cell.temperature = 20.0;
+            cell.soil_fertility = 0.5;
+        }
+    }
+    world
+}
+
+fn arability(world: &World, coast_distance: u32) -> f32 {
+    world.arability(&world.terrain[1][1], coast_distance)
+}
+
+#[test]
+fn coastal_plains_get_a_bonus() {
+    let world = sloped_world(0.0);
+    let inland = arability(&world, World::COASTAL_PLAIN_DISTANCE + 1);
+    assert_eq!(inland, 0.5);
+    assert_eq!(
+        arability(&world, World::COASTAL_PLAIN_DISTANCE),
+        inland * (1.0 + World::COASTAL_PLAIN_BONUS)
+    );
+    assert_eq!(arability(&world, 0), arability(&world, 1));
+}
+
+#[test]
+fn slopes_reduce_arability() {
+    let half = sloped_world(1.5 * World::MAX_ARABLE_SLOPE);
+    let slope = half.get_slant(&half.terrain[1][1]).abs();
+    assert!((slope - World::MAX_ARABLE_SLOPE / 2.0).abs() < 1e-3);
+    assert!((arability(&half, 100) - 0.25).abs() < 1e-6);
+
+    // Just below and just above the steepest farmable slope
+    let steep = sloped_world(2.97 * World::MAX_ARABLE_SLOPE);
+    assert!(arability(&steep, 100) > 0.0);
+    let too_steep = sloped_world(3.03 * World::MAX_ARABLE_SLOPE);
+    assert_eq!(arability(&too_steep, 100), 0.0);
+    let cliff = sloped_world(6.0 * World::MAX_ARABLE_SLOPE);
+    assert_eq!(arability(&cliff, 0), 0.0);
+}
+
+#[test]
+fn water_cannot_be_farmed() {
+    let mut world = sloped_world(0.0);
+    world.terrain[1][1].altitude = 0.0;
+    assert_eq!(arability(&world, 0), 0.0);
+}
@@ -0,0 +1,97 @@
+use {
+    planet::{
+        saving::{AutosaveSettings, Autosaves, SaveMetadata},
+        WorldManager,
+    },
+    std::{fs, path::PathBuf},
+};
+
+mod common;
+
+fn temp_directory(name: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("planet-{}-{name}", std::process::id()));
+    _ = fs::remove_dir_all(&directory);
+    directory
+}
+
+fn autosaved_titles(autosaves: &Autosaves) -> Vec<String> {
+    autosaves
+        .list()
+        .iter()
+        .map(|path| WorldManager::peek_save(path).unwrap().unwrap().info.title)
+        .collect()
+}
+
+#[test]
+fn rotates_autosaves() {
+    let directory = temp_directory("autosaves");
+    let autosaves = Autosaves::new(&directory);
+    let mut manager = common::load_fixture();
+    assert_eq!(autosaves.latest(), None);
+
+    for title in ["first", "second", "third", "fourth"] {
+        manager.info_mut().title = String::from(title);
+        autosaves.write(&manager, 3).unwrap();
+    }
+    assert_eq!(autosaved_titles(&autosaves), ["fourth", "third", "second"]);
+    assert_eq!(autosaves.latest(), Some(autosaves.slot_path(0)));
+
+    // Fewer slots drop the oldest autosaves
+    manager.info_mut().title = String::from("fifth");
+    autosaves.write(&manager, 2).unwrap();
+    assert_eq!(autosaved_titles(&autosaves), ["fifth", "fourth"]);
+
+    // Nothing is rotated if there is no world to save
+    assert!(autosaves.write(&WorldManager::new(), 2).is_err());
+    assert_eq!(autosaved_titles(&autosaves), ["fifth", "fourth"]);
+
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn notices_unclean_sessions() {
+    let directory = temp_directory("sessions");
+    let autosaves = Autosaves::new(&directory);
+
+    assert!(!autosaves.begin_session().unwrap());
+    autosaves.end_session().unwrap();
+    assert!(!autosaves.begin_session().unwrap());
+    // The session above never ends, like after a crash
+    assert!(autosaves.begin_session().unwrap());
+
+    let manager = common::load_fixture();
+    autosaves.write(&manager, 3).unwrap();
+    let recovered = autosaves.set_aside_latest().unwrap().unwrap();
+    assert_eq!(autosaves.latest(), None);
+
+    // New autosaves don't replace the recovered one
+    autosaves.write(&manager, 1).unwrap();
+    autosaves.write(&manager, 1).unwrap();
+    assert_eq!(
+        WorldManager::peek_save(&recovered).unwrap(),
+        Some(SaveMetadata::new(manager.get_world().unwrap(), manager.info()))
+    );
+
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn stores_settings() {
+    let directory = temp_directory("autosave-settings");
+    let autosaves = Autosaves::new(&directory);
+    assert_eq!(autosaves.load_settings(), AutosaveSettings::default());
+
+    let settings = AutosaveSettings {
+        enabled:          false,
+        interval_minutes: 12,
+        slots:            7,
+    };
+    autosaves.save_settings(&settings).unwrap();
+    assert_eq!(autosaves.load_settings(), settings);
+
+    fs::write(directory.join(AutosaveSettings::FILE_NAME), "not ron").unwrap();
+    assert_eq!(autosaves.load_settings(), AutosaveSettings::default());
+
+    fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,103 @@
+use planet::{BiomeType, TerrainCell, World};
+
+/// Presence of a biome in a cell of a one cell world
+fn presence(
+    biome: BiomeType,
+    altitude: f32,
+    temperature: f32,
+    slope: f32,
+    coast_distance: u32,
+) -> f32 {
+    let world = World::new(1, 1, 0);
+    let cell = TerrainCell {
+        altitude,
+        rainfall: 3000.0,
+        temperature,
+        ..Default::default()
+    };
+    world.biome_presence(&cell, biome.stats(), slope, coast_distance)
+}
+
+#[test]
+fn depth_limits_sea_biomes() {
+    // Shallow seas and reefs reach up to the surface, and deep oceans down to
+    // the lowest altitude
+    for (biome, shallowest, deepest) in [
+        (BiomeType::Ocean, Some(500.0), 7000.0),
+        (BiomeType::ShallowSea, None, 1000.0),
+        (BiomeType::DeepOcean, Some(6000.0), World::MAX_DEPTH),
+        (BiomeType::CoralReef, None, 300.0),
+    ] {
+        let at = |depth: f32| presence(biome, -depth, 25.0, 0.0, 0);
+        if let Some(shallowest) = shallowest {
+            assert!(at(shallowest) > 0.0, "{biome:?} at {shallowest} m");
+            assert_eq!(at(shallowest - 1.0), 0.0, "{biome:?} above {shallowest} m");
+        } else {
+            assert!(at(0.0) > 0.0, "{biome:?} at the surface");
+        }
+        assert!(at(deepest) > 0.0, "{biome:?} at {deepest} m");
+        if deepest < World::MAX_DEPTH {
+            assert_eq!(at(deepest + 1.0), 0.0, "{biome:?} below {deepest} m");
+        }
+    }
+}
+
+#[test]
+fn coast_distance_limits_coastal_biomes() {
+    let mangrove = |coast_distance| presence(BiomeType::Mangrove, 100.0, 26.0, 0.0, coast_distance);
+    assert!(mangrove(0) > 0.0);
+    assert!(mangrove(1) > 0.0);
+    assert_eq!(mangrove(2), 0.0);
+
+    let reef = |coast_distance| presence(BiomeType::CoralReef, -100.0, 26.0, 0.0, coast_distance);
+    assert!(reef(2) > 0.0);
+    assert_eq!(reef(3), 0.0);
+
+    // The open sea doesn't care how far the coast is
+    assert!(presence(BiomeType::Ocean, -3000.0, 10.0, 0.0, 1000) > 0.0);
+}
+
+#[test]
+fn slope_limits_wetlands() {
+    let wetland = |slope| presence(BiomeType::Wetland, 100.0, 10.0, slope, 5);
+    assert!(wetland(40.0) > 0.0);
+    assert_eq!(wetland(40.5), 0.0);
+}
+
+#[test]
+fn coast_distances_spread_from_the_coastline() {
+    // Land in the first four columns, water in the others
+    let (width, height) = (16, 5);
+    let mut world = World::new(width, height, 0);
+    for (y, row) in world.terrain.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            cell.x = x;
+            cell.y = y;
+            cell.altitude = if x < 4 { 100.0 } else { -100.0 };
+        }
+    }
+
+    // Both sides of the coast are at 0, including where rows wrap around
+    let expected = [0, 1, 1, 0, 0, 1, 2, 3, 4, 5, 5, 4, 3, 2, 1, 0];
+    for row in world.coast_distances() {
+        assert_eq!(row, expected);
+    }
+
+    for cell in world.terrain.iter_mut().flatten() {
+        cell.altitude = -100.0;
+    }
+    for row in world.coast_distances() {
+        assert!(row
+            .iter()
+            .all(|distance| *distance == World::MAX_COAST_DISTANCE));
+    }
+}
+
+#[test]
+fn stats_follow_biome_order() {
+    for biome in BiomeType::iterator() {
+        // Each biome's stats are named after its variant, split into words
+        let name: String = biome.stats().name.split(' ').collect();
+        assert_eq!(name, <&'static str>::from(biome));
+    }
+}
@@ -0,0 +1,12 @@
+//! Helpers shared by the integration tests
+
+use {planet::WorldManager, std::path::Path};
+
+/// Loads the version 6 save kept in `tests/fixtures`
+pub fn load_fixture() -> WorldManager {
+    let mut manager = WorldManager::new();
+    manager
+        .load_world(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/world_v6.rsplnt"))
+        .unwrap();
+    manager
+}
@@ -0,0 +1,61 @@
+use {
+    bevy::utils::HashSet,
+    planet::map::{self, ContourIntervals, MapStyle, WorldOverlay, WorldView},
+};
+
+mod common;
+
+#[test]
+fn isolines_follow_interval_crossings() {
+    let mut manager = common::load_fixture();
+    let world = manager.get_world_mut().unwrap();
+    // Rising by 100 m a cell from west to east, the same in every row
+    for cell in world.terrain.iter_mut().flatten() {
+        cell.altitude = cell.x as f32 * 100.0;
+    }
+    let world = manager.get_world().unwrap();
+
+    let lines: Vec<usize> = world.terrain[10]
+        .iter()
+        .filter(|cell| map::on_isoline(world, cell, 500.0, |cell| cell.altitude))
+        .map(|cell| cell.x)
+        .collect();
+    // Every 5 cells, and where the last column wraps around to the first
+    assert_eq!(lines, [4, 9, 14, 19, 24, 29, 34, 39]);
+
+    let lines = world.terrain[10]
+        .iter()
+        .filter(|cell| map::on_isoline(world, cell, 0.0, |cell| cell.altitude))
+        .count();
+    assert_eq!(lines, 0);
+}
+
+#[test]
+fn contours_are_drawn_over_the_view() {
+    let manager = common::load_fixture();
+    let world = manager.get_world().unwrap();
+    let style = MapStyle {
+        contours: ContourIntervals {
+            temperature: 2.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let plain = HashSet::default();
+    let mut overlays = HashSet::default();
+    _ = overlays.insert(WorldOverlay::TemperatureContours);
+
+    let mut lines = 0;
+    for cell in world.terrain.iter().flatten() {
+        let color = map::cell_color(world, cell, WorldView::Biomes, &overlays, &style);
+        let view = map::cell_color(world, cell, WorldView::Biomes, &plain, &style);
+        if map::on_isoline(world, cell, 2.0, |cell| cell.temperature) {
+            lines += 1;
+            assert_ne!(color, view);
+        } else {
+            // Unlike the other overlays, contours leave the view's colours
+            assert_eq!(color, view);
+        }
+    }
+    assert!(lines > 0);
+}
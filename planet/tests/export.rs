@@ -0,0 +1,111 @@
+use {
+    planet::{
+        export::{heightmap, map_image, DataLayer, ExportError, HeightmapFormat, MapImageOptions},
+        map::{WorldOverlay, WorldView},
+    },
+    std::fs,
+};
+
+mod common;
+
+#[test]
+fn exports_scaled_map_png() {
+    let manager = common::load_fixture();
+    let world = manager.get_world().unwrap();
+
+    for view in WorldView::iterator() {
+        let mut options = MapImageOptions {
+            view: *view,
+            scale: 3,
+            ..Default::default()
+        };
+        _ = options.overlays.insert(WorldOverlay::Rainfall);
+
+        let mut png = vec![];
+        map_image::write_map_png(world, &options, &mut png).unwrap();
+
+        let decoder = png::Decoder::new(png.as_slice());
+        let (info, mut reader) = decoder.read_info().unwrap();
+        assert_eq!((info.width, info.height), (120, 60));
+        assert_eq!(info.color_type, png::ColorType::RGB);
+        let mut pixels = vec![0; info.buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(pixels, map_image::map_pixels(world, &options).unwrap());
+
+        // Every cell is a 3x3 square of its colour
+        let row = &pixels[..info.width as usize * 3];
+        assert_eq!(row[..3], row[6..9]);
+        assert_eq!(&pixels[row.len()..row.len() * 2], row);
+    }
+}
+
+#[test]
+fn rejects_invalid_scales_before_writing() {
+    let manager = common::load_fixture();
+    let path = std::env::temp_dir().join(format!("planet-{}-scale.png", std::process::id()));
+    fs::write(&path, b"previous export").unwrap();
+
+    for scale in [0, u32::MAX / 8] {
+        let options = MapImageOptions {
+            scale,
+            ..Default::default()
+        };
+        assert!(matches!(
+            manager.export_map_png(&path, &options),
+            Err(ExportError::InvalidScale(invalid)) if invalid == scale
+        ));
+        assert_eq!(fs::read(&path).unwrap(), b"previous export");
+    }
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn exports_data_layers() {
+    let manager = common::load_fixture();
+    let world = manager.get_world().unwrap();
+    let directory = std::env::temp_dir().join(format!("planet-export-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+
+    let metadata = manager
+        .export_data(directory.join("world"), HeightmapFormat::Png)
+        .unwrap();
+    let files: Vec<&str> = metadata
+        .layers
+        .iter()
+        .map(|layer| layer.file.as_str())
+        .collect();
+    assert_eq!(
+        files,
+        [
+            "world.altitude.png",
+            "world.rainfall.npy",
+            "world.temperature.npy"
+        ]
+    );
+    assert!(directory.join("world.meta.ron").exists());
+    // Nothing but the outputs is left behind by writing them atomically
+    assert_eq!(fs::read_dir(&directory).unwrap().count(), files.len() + 1);
+
+    // The heightmap's first row is the northernmost one
+    let mut decoder = png::Decoder::new(fs::File::open(directory.join(files[0])).unwrap());
+    decoder.set_transformations(png::Transformations::IDENTITY);
+    let (info, mut reader) = decoder.read_info().unwrap();
+    assert_eq!(info.bit_depth, png::BitDepth::Sixteen);
+    let mut samples = vec![0; info.buffer_size()];
+    reader.next_frame(&mut samples).unwrap();
+    let north_west = world.terrain[world.height as usize - 1][0].altitude;
+    let altitude = heightmap::sample_to_altitude(u16::from_be_bytes([samples[0], samples[1]]));
+    assert!((altitude - north_west).abs() < 1.0);
+
+    let npy = fs::read(directory.join(files[2])).unwrap();
+    assert!(npy.starts_with(b"\x93NUMPY"));
+    let data_start = npy.len() - (world.width * world.height) as usize * 4;
+    assert_eq!(data_start % 64, 0);
+    let first = f32::from_le_bytes(npy[data_start..data_start + 4].try_into().unwrap());
+    assert_eq!(
+        first,
+        DataLayer::Temperature.value(&world.terrain[world.height as usize - 1][0])
+    );
+
+    fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,127 @@
+use {
+    bevy::utils::HashSet,
+    planet::{
+        map::{self, Hillshade, MapStyle, WorldView},
+        World,
+    },
+};
+
+mod common;
+
+/// Sets every cell's altitude from its position
+fn reshape(world: &mut World, altitude: impl Fn(usize, usize) -> f32) {
+    for cell in world.terrain.iter_mut().flatten() {
+        cell.altitude = altitude(cell.x, cell.y);
+    }
+}
+
+#[test]
+fn flat_terrain_is_lit_by_elevation() {
+    let mut manager = common::load_fixture();
+    let world = manager.get_world_mut().unwrap();
+    reshape(world, |_, _| 100.0);
+
+    let hillshade = Hillshade {
+        elevation: 30.0,
+        ..Default::default()
+    };
+    for cell in world.terrain.iter().flatten() {
+        assert!((hillshade.shade(world, cell) - 0.5).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn slopes_facing_the_light_are_brighter() {
+    let mut manager = common::load_fixture();
+    let world = manager.get_world_mut().unwrap();
+    // A ridge along the middle column, rising from west and east
+    reshape(world, |x, _| 5000.0 - 500.0 * (x as f32 - 20.0).abs());
+    let west_slope = &world.terrain[10][15];
+    let east_slope = &world.terrain[10][25];
+
+    let from_west = Hillshade {
+        azimuth: 270.0,
+        ..Default::default()
+    };
+    let from_east = Hillshade {
+        azimuth: 90.0,
+        ..Default::default()
+    };
+    let flat = (45.0_f32).to_radians().sin();
+    assert!(from_west.shade(world, west_slope) > flat);
+    assert!(from_west.shade(world, east_slope) < flat);
+    assert!(from_east.shade(world, east_slope) > flat);
+    assert!(from_east.shade(world, west_slope) < flat);
+
+    // Exaggerating the slopes makes the difference larger
+    let exaggerated = Hillshade {
+        exaggeration: 200.0,
+        ..from_west
+    };
+    assert!(exaggerated.shade(world, west_slope) > from_west.shade(world, west_slope));
+    assert!(exaggerated.shade(world, east_slope) < from_west.shade(world, east_slope));
+}
+
+#[test]
+fn relief_is_multiplied_over_views() {
+    let manager = common::load_fixture();
+    let world = manager.get_world().unwrap();
+    let overlays = HashSet::default();
+    let style = MapStyle {
+        hillshade: Hillshade {
+            multiply: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    for cell in world.terrain.iter().flatten() {
+        let shade = style.hillshade.shade(world, cell);
+        let relief = map::cell_color(world, cell, WorldView::Relief, &overlays, &style);
+        assert_eq!(relief.as_rgba_f32(), [shade, shade, shade, 1.0]);
+
+        let plain = map::cell_color(
+            world,
+            cell,
+            WorldView::Topography,
+            &overlays,
+            &MapStyle::default(),
+        );
+        let shaded = map::cell_color(world, cell, WorldView::Topography, &overlays, &style);
+        assert_eq!(shaded, plain * shade);
+    }
+}
+
+#[test]
+fn biomes_are_not_shaded_twice() {
+    let mut manager = common::load_fixture();
+    let world = manager.get_world_mut().unwrap();
+    reshape(world, |_, _| 100.0);
+    let overlays = HashSet::default();
+    // Lit from straight above, flat terrain isn't shaded at all
+    let style = MapStyle {
+        hillshade: Hillshade {
+            elevation: 90.0,
+            multiply: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    // The edge rows miss neighbours, which gives flat terrain a slant there
+    let inner_rows = &world.terrain[1..world.terrain.len() - 1];
+    for cell in inner_rows.iter().flatten() {
+        // Without the hillshade, even flat terrain is darkened by its slant
+        let plain = map::cell_color(
+            world,
+            cell,
+            WorldView::Biomes,
+            &overlays,
+            &MapStyle::default(),
+        );
+        let shaded = map::cell_color(world, cell, WorldView::Biomes, &overlays, &style);
+        for (shaded, plain) in shaded.as_rgba_f32().iter().zip(plain.as_rgba_f32()).take(3) {
+            assert!((shaded * 0.8 - plain).abs() < 1e-5);
+        }
+    }
+}
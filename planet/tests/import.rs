@@ -0,0 +1,80 @@
+use {
+    planet::{
+        export::{heightmap, HeightmapFormat},
+        import::{Heightmap, HeightmapOptions},
+        World,
+        WorldManager,
+    },
+    std::path::Path,
+};
+
+fn exported_heightmap(format: HeightmapFormat) -> (World, Heightmap) {
+    let mut manager = WorldManager::new();
+    manager
+        .load_world(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/world_v6.rsplnt"))
+        .unwrap();
+    let world = manager.get_world().unwrap();
+
+    let mut bytes = vec![];
+    heightmap::write_heightmap(world, format, &mut bytes).unwrap();
+    let heightmap = match format {
+        HeightmapFormat::Png => Heightmap::from_png(bytes.as_slice()),
+        HeightmapFormat::R16 => Heightmap::from_raw(&bytes, world.width, world.height),
+    }
+    .unwrap();
+
+    let mut generated = World::new(world.width, world.height, world.seed);
+    generated.terrain = world.terrain.clone();
+    (generated, heightmap)
+}
+
+#[test]
+fn imports_exported_heightmap() {
+    let (_, png) = exported_heightmap(HeightmapFormat::Png);
+    let (original, raw) = exported_heightmap(HeightmapFormat::R16);
+    assert_eq!(png, raw);
+
+    let (progress_sender, _) = crossbeam_channel::bounded(0);
+    let mut world = World::new(original.width, original.height, 7);
+    world
+        .generate_from_heightmap(&png, &HeightmapOptions::default(), &progress_sender)
+        .unwrap();
+
+    for (cell, original_cell) in world
+        .terrain
+        .iter()
+        .flatten()
+        .zip(original.terrain.iter().flatten())
+    {
+        assert!((cell.altitude - original_cell.altitude).abs() < 1.0);
+    }
+    assert!(world.max_rainfall > 0.0);
+    assert!(world
+        .terrain
+        .iter()
+        .flatten()
+        .all(|cell| !cell.biome_presences.is_empty()));
+}
+
+#[test]
+fn resamples_heightmap() {
+    let (original, heightmap) = exported_heightmap(HeightmapFormat::R16);
+    let options = HeightmapOptions {
+        sea_level:      0.25,
+        altitude_scale: 1000.0,
+    };
+
+    let (progress_sender, _) = crossbeam_channel::bounded(0);
+    let mut world = World::new(original.width * 2, original.height * 2, 7);
+    world
+        .generate_from_heightmap(&heightmap, &options, &progress_sender)
+        .unwrap();
+    assert_eq!(world.terrain.len(), original.height as usize * 2);
+    assert!(world.max_altitude <= 750.0);
+    assert!(world.min_altitude >= -250.0);
+
+    // Samples between cells are interpolated
+    let sample = heightmap.sample(0.5, 0.0);
+    let (a, b) = (heightmap.samples[0], heightmap.samples[1]);
+    assert!((sample - (a + b) / 2.0).abs() < 1e-6);
+}
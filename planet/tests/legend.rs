@@ -0,0 +1,92 @@
+use {
+    bevy::utils::HashSet,
+    planet::{
+        legend::{self, LegendContent, RAMP_STEPS},
+        map::{ContourIntervals, MapStyle, WorldOverlay, WorldView},
+        palette::BiomePalette,
+        BiomeType,
+    },
+};
+
+mod common;
+
+#[test]
+fn lists_view_then_overlays() {
+    let manager = common::load_fixture();
+    let world = manager.get_world().unwrap();
+    let mut overlays = HashSet::default();
+    _ = overlays.insert(WorldOverlay::TemperatureContours);
+    _ = overlays.insert(WorldOverlay::Rainfall);
+
+    let sections = legend::legend(world, WorldView::Biomes, &overlays, &MapStyle::default());
+    let titles: Vec<_> = sections.iter().map(|section| section.title).collect();
+    assert_eq!(titles, ["Biomes", "Rainfall", "Temperature contours"]);
+
+    let LegendContent::Swatches(biomes) = &sections[0].content else {
+        panic!("Biomes should be shown as swatches");
+    };
+    assert_eq!(biomes.len(), BiomeType::ITEM_COUNT);
+    for ((name, color), biome) in biomes.iter().zip(BiomeType::iterator()) {
+        assert_eq!(name, biome.stats().name);
+        assert_eq!(*color, BiomePalette::ColorBlindSafe.color(*biome));
+    }
+}
+
+#[test]
+fn ramps_have_ticks_at_round_values() {
+    let manager = common::load_fixture();
+    let world = manager.get_world().unwrap();
+    let mut overlays = HashSet::default();
+    _ = overlays.insert(WorldOverlay::Temperature);
+
+    let sections = legend::legend(
+        world,
+        WorldView::Topography,
+        &overlays,
+        &MapStyle::default(),
+    );
+    for section in &sections {
+        let LegendContent::Ramp { colors, ticks } = &section.content else {
+            panic!("{} should be shown as a ramp", section.title);
+        };
+        assert_eq!(colors.len(), RAMP_STEPS);
+        assert!(ticks.len() >= 2, "{}", section.title);
+        assert!(ticks
+            .windows(2)
+            .all(|pair| pair[0].position < pair[1].position));
+        assert!(ticks
+            .iter()
+            .all(|tick| (0.0..=1.0).contains(&tick.position)));
+    }
+
+    let LegendContent::Ramp { ticks, .. } = &sections[1].content else {
+        unreachable!();
+    };
+    let labels: Vec<_> = ticks.iter().map(|tick| tick.label.as_str()).collect();
+    assert_eq!(labels, ["-20 °C", "0 °C", "20 °C"]);
+}
+
+#[test]
+fn contours_show_their_interval() {
+    let manager = common::load_fixture();
+    let world = manager.get_world().unwrap();
+    let mut overlays = HashSet::default();
+    _ = overlays.insert(WorldOverlay::AltitudeContours);
+    let style = MapStyle {
+        contours: ContourIntervals {
+            altitude: 250.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let sections = legend::legend(world, WorldView::Coastlines, &overlays, &style);
+    assert!(matches!(
+        &sections[0].content,
+        LegendContent::Swatches(swatches) if swatches.len() == 5
+    ));
+    assert!(matches!(
+        &sections[1].content,
+        LegendContent::Contour { label, .. } if label == "Every 250 m"
+    ));
+}
@@ -0,0 +1,123 @@
+use {
+    bevy::utils::HashSet,
+    planet::{
+        color::Rgb,
+        legend::{self, LegendContent},
+        map::{self, MapColors, MapStyle, WorldOverlay, WorldView},
+        palette::{BiomePalette, ColorMap, ColorRamp, Palette, PaletteError},
+        BiomeType,
+    },
+};
+
+mod common;
+
+#[test]
+fn color_maps_run_between_their_ends() {
+    assert_eq!(
+        ColorMap::Viridis.sample(0.0).as_rgb_u8(),
+        [0x44, 0x01, 0x54]
+    );
+    assert_eq!(
+        ColorMap::Viridis.sample(1.0).as_rgb_u8(),
+        [0xfd, 0xe7, 0x25]
+    );
+    // Values outside of the scale are clamped to its ends
+    assert_eq!(ColorMap::Magma.sample(-1.0), ColorMap::Magma.sample(0.0));
+    assert_eq!(ColorMap::Magma.sample(2.0), ColorMap::Magma.sample(1.0));
+    assert_eq!(ColorMap::Greyscale.sample(0.5).as_rgb_u8(), [128, 128, 128]);
+
+    for map in ColorMap::iterator() {
+        let ramp = ColorRamp::Map(*map);
+        assert_eq!(ramp.sample(0.3), Some(map.sample(0.3)));
+        assert_eq!(ramp.name(), <&'static str>::from(map));
+    }
+    assert_eq!(ColorRamp::Classic.sample(0.3), None);
+}
+
+#[test]
+fn reads_palette_files() {
+    let palettes = Palette::from_ron(
+        r##"[
+            (name: "Sea to summit", colors: ["#000000", "#ff8000", "#FFFFFF"]),
+        ]"##,
+    )
+    .unwrap();
+    assert_eq!(palettes.len(), 1);
+    let palette = &palettes[0];
+    assert_eq!(palette.name, "Sea to summit");
+    assert_eq!(palette.sample(0.0), Rgb::BLACK);
+    assert_eq!(palette.sample(0.5).as_rgb_u8(), [255, 128, 0]);
+    assert_eq!(palette.sample(1.0), Rgb::WHITE);
+
+    assert!(matches!(
+        Palette::from_ron(r##"[(name: "Short", colors: ["#000000"])]"##),
+        Err(PaletteError::TooFewColors(name)) if name == "Short"
+    ));
+    assert!(matches!(
+        Palette::from_ron(r##"[(name: "Odd", colors: ["#000000", "red"])]"##),
+        Err(PaletteError::InvalidColor { color, .. }) if color == "red"
+    ));
+    assert!(matches!(
+        Palette::from_ron("[(name: \"Broken\""),
+        Err(PaletteError::InvalidRon(_))
+    ));
+}
+
+#[test]
+fn overlays_and_legend_use_the_picked_colors() {
+    let manager = common::load_fixture();
+    let world = manager.get_world().unwrap();
+    let mut overlays = HashSet::default();
+    _ = overlays.insert(WorldOverlay::Rainfall);
+    let style = MapStyle {
+        colors: MapColors {
+            rainfall: ColorRamp::Map(ColorMap::Cividis),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let cell = &world.terrain[10][10];
+    let plain = map::cell_color(world, cell, WorldView::Biomes, &HashSet::default(), &style);
+    let grey = (plain.r + plain.g + plain.b) / 3.0;
+    let rainfall = ColorMap::Cividis.sample(cell.rainfall / planet::World::MAX_RAINFALL);
+    let color = map::cell_color(world, cell, WorldView::Biomes, &overlays, &style);
+    assert_eq!(color, (Rgb::new(grey, grey, grey) + rainfall) * 0.5);
+
+    let sections = legend::legend(world, WorldView::Biomes, &overlays, &style);
+    let LegendContent::Ramp { colors, .. } = &sections[1].content else {
+        panic!("Rainfall should be shown as a ramp");
+    };
+    assert_eq!(colors.first(), Some(&ColorMap::Cividis.sample(0.0)));
+    assert_eq!(colors.last(), Some(&ColorMap::Cividis.sample(1.0)));
+}
+
+#[test]
+fn color_blind_safe_biome_colors_are_distinct() {
+    let colors: Vec<_> = BiomeType::iterator()
+        .map(|biome| BiomePalette::ColorBlindSafe.color(*biome).as_rgb_u8())
+        .collect();
+    for (index, color) in colors.iter().enumerate() {
+        assert!(!colors[index + 1..].contains(color));
+    }
+    for biome in BiomeType::iterator() {
+        assert_eq!(BiomePalette::Classic.color(*biome), biome.stats().color);
+    }
+
+    let manager = common::load_fixture();
+    let world = manager.get_world().unwrap();
+    let style = MapStyle {
+        colors: MapColors {
+            biomes: BiomePalette::ColorBlindSafe,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let sections = legend::legend(world, WorldView::Biomes, &HashSet::default(), &style);
+    let LegendContent::Swatches(swatches) = &sections[0].content else {
+        panic!("Biomes should be shown as swatches");
+    };
+    for ((_, color), biome) in swatches.iter().zip(BiomeType::iterator()) {
+        assert_eq!(*color, BiomePalette::ColorBlindSafe.color(*biome));
+    }
+}
@@ -0,0 +1,152 @@
+use planet::{
+    import::{
+        plnt::{self, GenerationStage},
+        ImportError,
+    },
+    BiomeType,
+};
+
+const SAVE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<World xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" Width="2" Height="2" Seed="-5" CurrentDate="0">
+  <TerrainCells>
+    <TerrainCell Longitude="0" Latitude="0" Altitude="-1200.5" Rainfall="0" Temperature="-20" Survivability="0">
+      <PresentBiomeNames><string>Ice Cap</string><string>Ocean</string></PresentBiomeNames>
+      <BiomePresences><float>0.75</float><float>0.25</float></BiomePresences>
+    </TerrainCell>
+    <TerrainCell Longitude="1" Latitude="0" Altitude="300" Rainfall="800" Temperature="12.5" Survivability="1">
+      <PresentBiomeNames><string>grassland</string><string>Desertic Tundra</string></PresentBiomeNames>
+      <BiomePresences><float>0.5</float><float>0.5</float></BiomePresences>
+    </TerrainCell>
+    <TerrainCell Longitude="0" Latitude="1" Altitude="2000" Rainfall="100" Temperature="-5" Survivability="0">
+      <PresentBiomeNames><string>tundra</string></PresentBiomeNames>
+      <BiomePresences><float>1</float></BiomePresences>
+    </TerrainCell>
+    <TerrainCell Longitude="1" Latitude="1" Altitude="150" Rainfall="1500" Temperature="20" Survivability="0">
+      <PresentBiomeNames><string>Rainforest Swamp</string></PresentBiomeNames>
+      <BiomePresences><float>1</float></BiomePresences>
+    </TerrainCell>
+  </TerrainCells>
+  <Polities />
+</World>"#;
+
+#[test]
+fn imports_original_save() {
+    let (world, report) = plnt::import_plnt(SAVE).unwrap();
+    assert_eq!((world.width, world.height), (2, 2));
+    assert_eq!(world.seed, -5_i32 as u32);
+
+    let cell = &world.terrain[0][0];
+    assert_eq!(
+        (cell.altitude, cell.rainfall, cell.temperature),
+        (-1200.5, 0.0, -20.0)
+    );
+    assert_eq!(
+        cell.biome_presences,
+        [(BiomeType::IceCap, 0.75), (BiomeType::Ocean, 0.25)]
+    );
+    assert_eq!(
+        world.terrain[0][1].biome_presences,
+        [(BiomeType::Grassland, 1.0)]
+    );
+    assert_eq!((world.min_altitude, world.max_altitude), (-1200.5, 2000.0));
+
+    // Without a known biome, the cell gets the ones its terrain supports
+    assert!(!world.terrain[1][1].biome_presences.is_empty());
+
+    assert!(!report.is_complete());
+    assert!(!report.regenerated_terrain);
+    assert_eq!(report.missing_cells, 0);
+    assert_eq!(
+        report.generated,
+        [
+            GenerationStage::Biomes,
+            GenerationStage::Arability,
+            GenerationStage::Resources
+        ]
+    );
+    assert_eq!(report.unknown_biomes["Desertic Tundra"], 1);
+    assert_eq!(report.unknown_biomes["Rainforest Swamp"], 1);
+    assert_eq!(report.unmapped["TerrainCell@Survivability"], 4);
+    assert_eq!(report.unmapped["World@CurrentDate"], 1);
+    assert_eq!(report.unmapped["World/Polities"], 1);
+    assert!(!report.unmapped.contains_key("World/TerrainCells"));
+}
+
+#[test]
+fn regenerates_terrain_missing_from_save() {
+    let (world, report) =
+        plnt::import_plnt(r#"<World Width="8" Height="4" Seed="3"></World>"#).unwrap();
+    assert!(report.regenerated_terrain);
+    assert_eq!(report.missing_cells, 32);
+    assert!(world.max_altitude > world.min_altitude);
+}
+
+#[test]
+fn fills_cells_missing_from_save() {
+    let (world, report) = plnt::import_plnt(
+        r#"<World Width="8" Height="4" Seed="3">
+          <TerrainCell Longitude="0" Latitude="0" Altitude="100" Rainfall="500" Temperature="10">
+            <PresentBiomeNames><string>Grassland</string></PresentBiomeNames>
+            <BiomePresences><float>1</float></BiomePresences>
+          </TerrainCell>
+        </World>"#,
+    )
+    .unwrap();
+    assert_eq!(report.missing_cells, 31);
+    assert_eq!(report.generated[0], GenerationStage::Terrain);
+
+    // The missing cells are the ones generated from the seed
+    let (generated, _) =
+        plnt::import_plnt(r#"<World Width="8" Height="4" Seed="3"></World>"#).unwrap();
+    assert_eq!(world.terrain[0][0].altitude, 100.0);
+    assert_eq!(world.terrain[2][5].altitude, generated.terrain[2][5].altitude);
+    for cell in world.terrain.iter().flatten() {
+        assert!(!cell.biome_presences.is_empty());
+    }
+}
+
+#[test]
+fn rejects_mismatched_biomes() {
+    let xml = r#"<World Width="1" Height="1" Seed="3">
+      <TerrainCell Longitude="0" Latitude="0" Altitude="100" Rainfall="500" Temperature="10">
+        <PresentBiomeNames><string>Grassland</string><string>Taiga</string></PresentBiomeNames>
+        <BiomePresences><float>1</float></BiomePresences>
+      </TerrainCell>
+    </World>"#;
+    assert!(matches!(
+        plnt::import_plnt(xml),
+        Err(ImportError::InvalidPlnt(_))
+    ));
+}
+
+#[test]
+fn rejects_invalid_save() {
+    assert!(matches!(
+        plnt::import_plnt("<Planet />"),
+        Err(ImportError::InvalidPlnt(_))
+    ));
+    assert!(matches!(
+        plnt::import_plnt(r#"<World Width="2" Seed="1" />"#),
+        Err(ImportError::InvalidPlnt(_))
+    ));
+    assert!(matches!(
+        plnt::import_plnt("<World"),
+        Err(ImportError::InvalidXml(_))
+    ));
+}
+
+#[test]
+fn rejects_oversized_world() {
+    let (max_width, max_height) = plnt::MAX_WORLD_SIZE;
+    for (width, height) in [
+        (max_width + 1, 1),
+        (1, max_height + 1),
+        (u32::MAX, u32::MAX),
+    ] {
+        let xml = format!(r#"<World Width="{width}" Height="{height}" Seed="1"></World>"#);
+        assert!(matches!(
+            plnt::import_plnt(&xml),
+            Err(ImportError::InvalidPlnt(_))
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+use {
+    bevy::utils::HashSet,
+    planet::{
+        map::{self, MapStyle, WorldView},
+        projection::{MapProjection, Projection},
+        WorldManager,
+    },
+    std::path::Path,
+};
+
+const WIDTH: u32 = 40;
+const HEIGHT: u32 = 20;
+
+fn projection(kind: MapProjection) -> Projection {
+    Projection {
+        kind,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn equirectangular_maps_pixels_to_cells() {
+    let cells: Vec<_> = projection(MapProjection::Equirectangular)
+        .resample(WIDTH, HEIGHT)
+        .collect();
+    let expected: Vec<_> = (0..HEIGHT as usize)
+        .rev()
+        .flat_map(|y| (0..WIDTH as usize).map(move |x| Some((x, y))))
+        .collect();
+    assert_eq!(cells, expected);
+}
+
+#[test]
+fn centre_longitude_turns_the_map() {
+    let projection = Projection {
+        center_longitude: 90.0,
+        ..projection(MapProjection::Equirectangular)
+    };
+    // The west edge is now at -90 degrees, a quarter of the way around
+    assert_eq!(projection.cell_at(WIDTH, HEIGHT, 0.5, 10.5), Some((10, 9)));
+    assert_eq!(projection.cell_at(WIDTH, HEIGHT, 39.5, 10.5), Some((9, 9)));
+}
+
+#[test]
+fn projections_only_cover_the_world() {
+    for kind in [
+        MapProjection::Mollweide,
+        MapProjection::Robinson,
+        MapProjection::Sinusoidal,
+        MapProjection::Orthographic,
+    ] {
+        let projection = projection(kind);
+        let cell_at = |x, y| projection.cell_at(WIDTH, HEIGHT, x, y);
+
+        assert_eq!(cell_at(0.5, 0.5), None, "{kind:?}");
+        assert_eq!(cell_at(39.5, 19.5), None, "{kind:?}");
+        assert_eq!(cell_at(-1.0, 10.0), None, "{kind:?}");
+        assert_eq!(cell_at(20.0, 10.0), Some((20, 10)), "{kind:?}");
+
+        // The poles are at the top and bottom of the map
+        assert_eq!(cell_at(20.0, 0.1).map(|(_, y)| y), Some(19), "{kind:?}");
+        assert_eq!(cell_at(20.0, 19.9).map(|(_, y)| y), Some(0), "{kind:?}");
+    }
+
+    // Except for the globe, the equator spans the whole width
+    for kind in [
+        MapProjection::Mollweide,
+        MapProjection::Robinson,
+        MapProjection::Sinusoidal,
+    ] {
+        let projection = projection(kind);
+        assert_eq!(
+            projection.cell_at(WIDTH, HEIGHT, 0.1, 10.0),
+            Some((0, 10)),
+            "{kind:?}"
+        );
+        assert_eq!(
+            projection.cell_at(WIDTH, HEIGHT, 39.9, 10.0),
+            Some((39, 10)),
+            "{kind:?}"
+        );
+    }
+}
+
+#[test]
+fn globe_can_be_turned_and_tilted() {
+    let projection = Projection {
+        kind:             MapProjection::Orthographic,
+        center_longitude: 90.0,
+        center_latitude:  45.0,
+    };
+    assert_eq!(
+        projection.cell_at(WIDTH, HEIGHT, 20.0, 10.0),
+        Some((30, 15))
+    );
+    // Tilted towards the north pole, which is visible near the top
+    let (_, y) = projection.cell_at(WIDTH, HEIGHT, 20.0, 4.0).unwrap();
+    assert_eq!(y, HEIGHT as usize - 1);
+    // The edge of the globe is as high as the map
+    assert!(projection.cell_at(WIDTH, HEIGHT, 20.0, 0.1).is_some());
+    assert!(projection.cell_at(WIDTH, HEIGHT, 9.9, 10.0).is_none());
+}
+
+#[test]
+fn projected_colors_match_unprojected_map() {
+    let mut manager = WorldManager::new();
+    manager
+        .load_world(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("fixtures")
+                .join("world_v6.rsplnt"),
+        )
+        .unwrap();
+    let world = manager.get_world().unwrap();
+
+    let overlays = HashSet::default();
+    let colors: Vec<_> =
+        map::map_colors(world, WorldView::Biomes, &overlays, &MapStyle::default()).collect();
+    let projected: Vec<_> =
+        map::projected_map_colors(world, &colors, &projection(MapProjection::Equirectangular))
+            .collect();
+    assert_eq!(
+        projected,
+        colors.iter().copied().map(Some).collect::<Vec<_>>()
+    );
+
+    let projected: Vec<_> =
+        map::projected_map_colors(world, &colors, &projection(MapProjection::Mollweide)).collect();
+    assert_eq!(projected.len(), colors.len());
+    assert!(projected.contains(&None));
+}
@@ -0,0 +1,97 @@
+use {
+    planet::{
+        saving::{recipe::GENERATOR_VERSION, WorldRecipe},
+        world_manager::{SaveError, WorldOrigin},
+        World,
+        WorldManager,
+    },
+    std::{fs, path::Path},
+};
+
+#[test]
+fn recipe_regenerates_world() {
+    let (progress_sender, _) = crossbeam_channel::bounded(0);
+    let mut world = World::new(40, 20, 99);
+    world.resource_scarcity = 2.0;
+    world.generate(&progress_sender).unwrap();
+
+    let recipe = WorldRecipe::new(&world);
+    let parsed = WorldRecipe::from_ron(&recipe.to_ron().unwrap()).unwrap();
+    assert_eq!(recipe, parsed);
+    assert!(parsed.matches_generator());
+
+    let mut regenerated = parsed.world();
+    regenerated.generate(&progress_sender).unwrap();
+    assert_eq!(
+        postcard::to_stdvec(&world).unwrap(),
+        postcard::to_stdvec(&regenerated).unwrap()
+    );
+}
+
+#[test]
+fn reads_handwritten_recipe() {
+    let recipe = WorldRecipe::from_ron(
+        r#"(
+            generator_version: "0.0.0",
+            seed: 7,
+            width: 80,
+            height: 40,
+        )"#,
+    )
+    .unwrap();
+    assert_ne!(recipe.generator_version, GENERATOR_VERSION);
+    assert!(!recipe.matches_generator());
+    assert_eq!(recipe.resource_scarcity, World::DEFAULT_RESOURCE_SCARCITY);
+
+    let world = recipe.world();
+    assert_eq!((world.width, world.height, world.seed), (80, 40, 7));
+}
+
+#[test]
+fn rejects_invalid_resource_scarcity() {
+    for scarcity in ["0.0", "-1.0", "inf", "NaN"] {
+        let recipe = format!(
+            "(generator_version: \"0.0.0\", seed: 7, width: 80, height: 40, resource_scarcity: \
+             {scarcity})"
+        );
+        assert!(WorldRecipe::from_ron(&recipe).is_err(), "{scarcity}");
+    }
+
+    let mut world = World::new(8, 4, 7);
+    world.resource_scarcity = -1.0;
+    let (progress_sender, _) = crossbeam_channel::bounded(0);
+    assert!(world.generate(&progress_sender).is_err());
+}
+
+#[test]
+fn only_generated_worlds_have_recipes() {
+    let path = std::env::temp_dir().join(format!("planet-{}-origin.ron", std::process::id()));
+    let mut manager = WorldManager::new();
+    manager
+        .load_world(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/world_v6.rsplnt"))
+        .unwrap();
+    assert!(!manager.can_save_recipe());
+    assert!(matches!(
+        manager.save_recipe(&path),
+        Err(SaveError::NotGenerated(WorldOrigin::Loaded))
+    ));
+    assert!(!path.exists());
+
+    let world = manager.get_world().unwrap();
+    let mut heightmap_world = World::new(world.width, world.height, world.seed);
+    heightmap_world.terrain = world.terrain.clone();
+    manager.set_world(heightmap_world, WorldOrigin::Heightmap);
+    assert!(matches!(
+        manager.save_recipe(&path),
+        Err(SaveError::NotGenerated(WorldOrigin::Heightmap))
+    ));
+
+    let (progress_sender, _) = crossbeam_channel::bounded(0);
+    let mut generated = World::new(8, 4, 7);
+    generated.generate(&progress_sender).unwrap();
+    manager.set_world(generated, WorldOrigin::Generated);
+    manager.save_recipe(&path).unwrap();
+    let recipe = manager.load_recipe(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!((recipe.width, recipe.height, recipe.seed), (8, 4, 7));
+}
@@ -0,0 +1,49 @@
+use planet::World;
+
+fn generate(seed: u32, resource_scarcity: f32) -> World {
+    let (progress_sender, _) = crossbeam_channel::bounded(0);
+    let mut world = World::new(80, 40, seed);
+    world.resource_scarcity = resource_scarcity;
+    world.generate(&progress_sender).unwrap();
+    world
+}
+
+fn deposit_count(world: &World) -> usize {
+    world
+        .terrain
+        .iter()
+        .flatten()
+        .map(|cell| cell.resources.len())
+        .sum()
+}
+
+#[test]
+fn resources_depend_only_on_the_seed() {
+    let resources = |world: &World| -> Vec<_> {
+        world
+            .terrain
+            .iter()
+            .flatten()
+            .map(|cell| cell.resources.clone())
+            .collect()
+    };
+    let world = generate(3, World::DEFAULT_RESOURCE_SCARCITY);
+    assert!(deposit_count(&world) > 0);
+    assert_eq!(
+        resources(&world),
+        resources(&generate(3, World::DEFAULT_RESOURCE_SCARCITY))
+    );
+    assert_ne!(
+        resources(&world),
+        resources(&generate(4, World::DEFAULT_RESOURCE_SCARCITY))
+    );
+}
+
+#[test]
+fn scarcity_makes_resources_rarer() {
+    let common = deposit_count(&generate(3, 0.5));
+    let default = deposit_count(&generate(3, World::DEFAULT_RESOURCE_SCARCITY));
+    let rare = deposit_count(&generate(3, 4.0));
+    assert!(common > default, "{common} deposits at 0.5, {default} at 1");
+    assert!(default > rare, "{default} deposits at 1, {rare} at 4");
+}
@@ -0,0 +1,72 @@
+use {
+    planet::saving::{json, tabular, SaveFormat},
+    rand::{Rng, SeedableRng},
+    rand_chacha::ChaCha12Rng,
+};
+
+mod common;
+
+#[test]
+fn json_round_trip() {
+    let manager = common::load_fixture();
+    let mut world = manager.get_world().unwrap().clone();
+    let world = &mut world;
+    _ = world.rng.gen::<u64>();
+
+    let serialized = json::to_json(world).unwrap();
+    let loaded = json::from_json(&serialized).unwrap();
+    assert_eq!(
+        postcard::to_stdvec(world).unwrap(),
+        postcard::to_stdvec(&loaded).unwrap()
+    );
+    assert_eq!(loaded.max_altitude, world.max_altitude);
+    // The loaded world continues like the saved one
+    assert_eq!(loaded.rng, world.rng);
+
+    // Hand edits are picked up
+    let edited = serialized.replacen("\"resource_scarcity\": 1.0", "\"resource_scarcity\": 3.5", 1);
+    assert_eq!(json::from_json(&edited).unwrap().resource_scarcity, 3.5);
+
+    // JSON worlds written without the RNG continue from their seed
+    let without_rng = serde_json::to_string(world).unwrap();
+    let loaded = json::from_json(&without_rng).unwrap();
+    assert_eq!(loaded.rng, ChaCha12Rng::seed_from_u64(u64::from(world.seed)));
+}
+
+#[test]
+fn csv_has_a_row_per_cell() {
+    let manager = common::load_fixture();
+    let world = manager.get_world().unwrap();
+
+    let mut table = vec![];
+    tabular::write_csv(world, &mut table).unwrap();
+    let table = String::from_utf8(table).unwrap();
+    let mut lines = table.lines();
+
+    let header: Vec<&str> = lines.next().unwrap().split(',').collect();
+    assert_eq!(header[..tabular::CELL_COLUMNS.len()], tabular::CELL_COLUMNS);
+    assert!(header.contains(&"Ice Cap"));
+    assert_eq!(lines.clone().count(), (world.width * world.height) as usize);
+
+    let first: Vec<f32> = lines
+        .next()
+        .unwrap()
+        .split(',')
+        .map(|value| value.parse().unwrap())
+        .collect();
+    assert_eq!(first.len(), header.len());
+    assert_eq!(first[..4], [0.0, 0.0, -90.0, -180.0]);
+    assert_eq!(first[4], world.terrain[0][0].altitude);
+    let presences: f32 = first[tabular::CELL_COLUMNS.len()..].iter().sum();
+    assert!((presences - 1.0).abs() < 1e-3);
+}
+
+#[test]
+fn format_from_extension() {
+    assert_eq!(SaveFormat::from_path("world.rsplnt"), SaveFormat::Binary);
+    assert_eq!(SaveFormat::from_path("world.ron"), SaveFormat::Recipe);
+    assert_eq!(SaveFormat::from_path("world.JSON"), SaveFormat::Json);
+    assert_eq!(SaveFormat::from_path("world.csv"), SaveFormat::Csv);
+    assert_eq!(SaveFormat::from_path("world"), SaveFormat::Binary);
+    assert!(!SaveFormat::Csv.can_load());
+}
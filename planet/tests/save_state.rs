@@ -0,0 +1,74 @@
+use {
+    planet::{world_manager::WorldOrigin, World, WorldManager},
+    std::fs,
+};
+
+fn generated_world() -> World {
+    let (progress_sender, _) = crossbeam_channel::bounded(0);
+    let mut world = World::new(40, 20, 1234);
+    world.generate(&progress_sender).unwrap();
+    world
+}
+
+/// Stands in for advancing the simulation: the next iteration regenerates
+/// the world from wherever the RNG left off
+fn continue_world(world: &mut World) {
+    let (progress_sender, _) = crossbeam_channel::bounded(0);
+    world.iteration += 1;
+    world.generate(&progress_sender).unwrap();
+}
+
+fn saved_bytes(manager: &WorldManager, name: &str) -> Vec<u8> {
+    let path = std::env::temp_dir().join(format!("planet-{}-{name}", std::process::id()));
+    manager.save_world(&path).unwrap();
+    let bytes = fs::read(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    bytes
+}
+
+#[test]
+fn loaded_world_continues_identically() {
+    let mut in_place = WorldManager::new();
+    in_place.set_world(generated_world(), WorldOrigin::Generated);
+
+    let mut saved = WorldManager::new();
+    saved.set_world(generated_world(), WorldOrigin::Generated);
+    let path = std::env::temp_dir().join(format!("planet-{}-state.rsplnt", std::process::id()));
+    saved.save_world(&path).unwrap();
+    let mut loaded = WorldManager::new();
+    loaded.load_world(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    // Only the simulation state is compared, not when each world was created
+    *loaded.info_mut() = in_place.info().clone();
+
+    for _ in 0..2 {
+        assert_eq!(
+            in_place.get_world().unwrap().rng,
+            loaded.get_world().unwrap().rng
+        );
+        assert_eq!(
+            saved_bytes(&in_place, "in-place.rsplnt"),
+            saved_bytes(&loaded, "loaded.rsplnt")
+        );
+
+        continue_world(in_place.get_world_mut().unwrap());
+        continue_world(loaded.get_world_mut().unwrap());
+    }
+}
+
+#[test]
+fn world_revision_follows_the_world() {
+    let mut manager = WorldManager::new();
+    manager.set_world(generated_world(), WorldOrigin::Generated);
+    let revision = manager.world_revision();
+    _ = manager.get_world().unwrap();
+    assert_eq!(manager.world_revision(), revision);
+
+    // A world generated from the same seed is still another world
+    manager.set_world(generated_world(), WorldOrigin::Generated);
+    let replaced = manager.world_revision();
+    assert_ne!(replaced, revision);
+
+    continue_world(manager.get_world_mut().unwrap());
+    assert_ne!(manager.world_revision(), replaced);
+}
@@ -0,0 +1,363 @@
+use {
+    planet::{
+        saving::{
+            SaveCompression,
+            SaveHeader,
+            SaveMetadata,
+            SaveOptions,
+            CURRENT_SAVE_VERSION,
+            SAVE_MAGIC,
+        },
+        world_manager::{LoadError, SaveError},
+        World,
+        WorldManager,
+    },
+    std::{
+        fs,
+        io::Cursor,
+        path::{Path, PathBuf},
+    },
+};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn temp_save(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("planet-{}-{name}", std::process::id()))
+}
+
+fn load(path: &Path) -> Result<WorldManager, LoadError> {
+    let mut manager = WorldManager::new();
+    manager.load_world(path)?;
+    Ok(manager)
+}
+
+/// Saves the loaded world again, and checks that the new save is in the
+/// current format and loads back to the same world
+fn assert_round_trip(manager: &WorldManager, name: &str) -> Vec<u8> {
+    let path = temp_save(name);
+    let report = manager.save_world(&path).expect("Failed to save world");
+    let saved = fs::read(&path).unwrap();
+    assert_eq!(report.file_size, saved.len());
+    assert_eq!(
+        SaveHeader::from_bytes(&saved).unwrap(),
+        Some(SaveHeader::new(manager.save_options()))
+    );
+
+    let mut reloaded = load(&path).expect("Failed to reload saved world");
+    reloaded.set_save_options(manager.save_options());
+    reloaded
+        .save_world(&path)
+        .expect("Failed to save reloaded world");
+    assert_eq!(saved, fs::read(&path).unwrap());
+    fs::remove_file(&path).unwrap();
+
+    saved
+}
+
+fn assert_terrain_close(world: &World, other: &World, tolerance: f32) {
+    for (cell, other_cell) in world
+        .terrain
+        .iter()
+        .flatten()
+        .zip(other.terrain.iter().flatten())
+    {
+        assert!((cell.altitude - other_cell.altitude).abs() <= tolerance);
+        assert!((cell.rainfall - other_cell.rainfall).abs() <= tolerance);
+        assert!((cell.temperature - other_cell.temperature).abs() <= tolerance);
+        assert_eq!(cell.biome_presences, other_cell.biome_presences);
+        assert_eq!(cell.resources, other_cell.resources);
+    }
+}
+
+#[test]
+fn loads_version_0() {
+    let manager = load(&fixture("world_v0.rsplnt")).expect("Failed to load version 0 save");
+    let world = manager.get_world().unwrap();
+    assert_eq!((world.width, world.height, world.seed), (40, 20, 42));
+    assert_eq!(world.terrain.len(), 20);
+    assert!(world.terrain.iter().all(|row| row.len() == 40));
+
+    // Fields introduced in version 1 are derived when upgrading
+    let cells = || world.terrain.iter().flatten();
+    assert!(cells().any(|cell| cell.arability > 0.0));
+    assert!(cells().any(|cell| !cell.resources.is_empty()));
+
+    assert_round_trip(&manager, "v0.rsplnt");
+}
+
+#[test]
+fn loads_version_1() {
+    let manager = load(&fixture("world_v1.rsplnt")).expect("Failed to load version 1 save");
+    let world = manager.get_world().unwrap();
+    assert_eq!((world.width, world.height, world.seed), (40, 20, 42));
+
+    let current = load(&fixture("world_v6.rsplnt")).unwrap();
+    assert_terrain_close(world, current.get_world().unwrap(), 0.0);
+
+    assert_round_trip(&manager, "v1.rsplnt");
+}
+
+#[test]
+fn loads_version_2() {
+    let manager = load(&fixture("world_v2.rsplnt")).expect("Failed to load version 2 save");
+    let world = manager.get_world().unwrap();
+    assert_eq!((world.width, world.height, world.seed), (40, 20, 42));
+
+    let current = load(&fixture("world_v6.rsplnt")).unwrap();
+    assert_terrain_close(world, current.get_world().unwrap(), 0.0);
+
+    assert_round_trip(&manager, "v2.rsplnt");
+}
+
+#[test]
+fn loads_version_3() {
+    let manager = load(&fixture("world_v3.rsplnt")).expect("Failed to load version 3 save");
+    let world = manager.get_world().unwrap();
+    assert_eq!((world.width, world.height, world.seed), (40, 20, 42));
+
+    let current = load(&fixture("world_v6.rsplnt")).unwrap();
+    let current_world = current.get_world().unwrap();
+    assert_terrain_close(world, current_world, 0.0);
+    assert_eq!(world.rng, current_world.rng);
+
+    assert_round_trip(&manager, "v3.rsplnt");
+}
+
+#[test]
+fn loads_version_4() {
+    let manager = load(&fixture("world_v4.rsplnt")).expect("Failed to load version 4 save");
+    let world = manager.get_world().unwrap();
+    assert_eq!((world.width, world.height, world.seed), (40, 20, 42));
+    assert_eq!(manager.info().title, "Test fixture");
+
+    let current = load(&fixture("world_v6.rsplnt")).unwrap();
+    assert_terrain_close(world, current.get_world().unwrap(), 0.0);
+
+    assert_round_trip(&manager, "v4.rsplnt");
+}
+
+#[test]
+fn loads_version_5() {
+    let manager = load(&fixture("world_v5.rsplnt")).expect("Failed to load version 5 save");
+    let world = manager.get_world().unwrap();
+    assert_eq!((world.width, world.height, world.seed), (40, 20, 42));
+    assert_eq!(manager.info().title, "Test fixture");
+
+    let current = load(&fixture("world_v6.rsplnt")).unwrap();
+    let current_world = current.get_world().unwrap();
+    assert_terrain_close(world, current_world, 0.0);
+    assert_eq!(world.rng, current_world.rng);
+
+    assert_round_trip(&manager, "v5.rsplnt");
+}
+
+#[test]
+fn loads_quantized_version_5() {
+    let path = fixture("world_v5_quantized.rsplnt");
+    let manager = load(&path).expect("Failed to load quantized version 5 save");
+    let world = manager.get_world().unwrap();
+    assert_eq!((world.width, world.height, world.seed), (40, 20, 42));
+
+    let current = load(&fixture("world_v6.rsplnt")).unwrap();
+    let current_world = current.get_world().unwrap();
+    assert_terrain_close(world, current_world, 0.5);
+    assert_eq!(world.rng, current_world.rng);
+}
+
+#[test]
+fn loads_version_6() {
+    let path = fixture("world_v6.rsplnt");
+    let manager = load(&path).expect("Failed to load version 6 save");
+    let world = manager.get_world().unwrap();
+    assert_eq!((world.width, world.height, world.seed), (40, 20, 42));
+    assert_eq!(manager.info().title, "Test fixture");
+
+    // The newest fixture must be byte for byte what the current format
+    // writes, so the layout can't change without a version bump
+    assert_eq!(CURRENT_SAVE_VERSION, 6);
+    let saved = assert_round_trip(&manager, "v6.rsplnt");
+    assert_eq!(saved, fs::read(&path).unwrap());
+}
+
+#[test]
+fn peeks_save_metadata() {
+    let metadata = WorldManager::peek_save(fixture("world_v6.rsplnt"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(metadata.info.title, "Test fixture");
+    assert_eq!(
+        (metadata.seed, metadata.width, metadata.height),
+        (42, 40, 20)
+    );
+    let preview = &metadata.preview;
+    assert_eq!((preview.width, preview.height), (40, 20));
+    assert_eq!(preview.pixels.len(), 40 * 20 * 3);
+
+    let loaded = load(&fixture("world_v6.rsplnt")).unwrap();
+    assert_eq!(
+        metadata,
+        SaveMetadata::new(loaded.get_world().unwrap(), loaded.info())
+    );
+
+    // Older saves have nothing to describe them with
+    for old_save in ["world_v0.rsplnt", "world_v2.rsplnt"] {
+        assert_eq!(WorldManager::peek_save(fixture(old_save)).unwrap(), None);
+    }
+}
+
+#[test]
+fn reads_save_with_progress() {
+    let save = fs::read(fixture("world_v6.rsplnt")).unwrap();
+    let (progress_sender, progress_receiver) = crossbeam_channel::unbounded();
+    let (world, info) = WorldManager::read_save(Cursor::new(save), &progress_sender).unwrap();
+    assert_eq!((world.width, world.height, world.seed), (40, 20, 42));
+    assert_eq!(info.title, "Test fixture");
+
+    let progress: Vec<(f32, String)> = progress_receiver.try_iter().collect();
+    assert!(progress
+        .windows(2)
+        .all(|pair| pair[0].0 <= pair[1].0 && pair[1].0 <= 1.0));
+    assert!(progress
+        .iter()
+        .any(|(value, text)| text == "Loading terrain" && *value == 1.0));
+
+    // Reading a save leaves the current world alone until it is set
+    let mut manager = WorldManager::new();
+    assert!(manager.get_world().is_none());
+    manager.set_loaded_world(world, info);
+    assert_eq!(manager.info().title, "Test fixture");
+    let loaded = load(&fixture("world_v6.rsplnt")).unwrap();
+    assert_terrain_close(
+        manager.get_world().unwrap(),
+        loaded.get_world().unwrap(),
+        0.0,
+    );
+}
+
+#[test]
+fn saves_with_every_option() {
+    let mut manager = load(&fixture("world_v6.rsplnt")).unwrap();
+    let original = load(&fixture("world_v6.rsplnt")).unwrap();
+
+    for compression in SaveCompression::iterator() {
+        for quantize in [false, true] {
+            manager.set_save_options(SaveOptions {
+                compression: *compression,
+                quantize,
+            });
+            let name = format!("{compression:?}-{quantize}.rsplnt");
+            assert_round_trip(&manager, &name);
+
+            let path = temp_save(&name);
+            manager.save_world(&path).unwrap();
+            let reloaded = load(&path).unwrap();
+            fs::remove_file(&path).unwrap();
+
+            let tolerance = if quantize { 0.5 } else { 0.0 };
+            assert_terrain_close(
+                original.get_world().unwrap(),
+                reloaded.get_world().unwrap(),
+                tolerance,
+            );
+        }
+    }
+}
+
+#[test]
+fn rejects_unsupported_version() {
+    let mut save = fs::read(fixture("world_v6.rsplnt")).unwrap();
+    save[SAVE_MAGIC.len()..SAVE_MAGIC.len() + 2].copy_from_slice(&u16::MAX.to_le_bytes());
+    let path = temp_save("unsupported.rsplnt");
+    fs::write(&path, save).unwrap();
+
+    let result = load(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(
+        result,
+        Err(LoadError::UnsupportedVersion(u16::MAX))
+    ));
+}
+
+#[test]
+fn rejects_corrupted_saves() {
+    let save = fs::read(fixture("world_v6.rsplnt")).unwrap();
+    let path = temp_save("corrupted.rsplnt");
+
+    let truncated = &save[..save.len() / 2];
+    fs::write(&path, truncated).unwrap();
+    assert!(matches!(load(&path), Err(LoadError::Corrupted)));
+
+    let mut flipped = save.clone();
+    flipped[save.len() / 2] ^= 1;
+    fs::write(&path, flipped).unwrap();
+    assert!(matches!(load(&path), Err(LoadError::Corrupted)));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn rejects_corrupted_metadata() {
+    let save = fs::read(fixture("world_v6.rsplnt")).unwrap();
+    let header_length = SaveHeader::new(SaveOptions::default()).length();
+    let path = temp_save("metadata.rsplnt");
+
+    // A block length far beyond the end of the file
+    let mut too_long = save[..header_length].to_vec();
+    too_long.extend(u32::MAX.to_le_bytes());
+    too_long.extend(&save[header_length + 4..header_length + 64]);
+    fs::write(&path, too_long).unwrap();
+    assert!(matches!(
+        WorldManager::peek_save(&path),
+        Err(LoadError::InvalidSave(_))
+    ));
+    fs::remove_file(&path).unwrap();
+
+    let manager = load(&fixture("world_v6.rsplnt")).unwrap();
+    let metadata = SaveMetadata::new(manager.get_world().unwrap(), manager.info());
+    for break_preview in [
+        |metadata: &mut SaveMetadata| metadata.preview.width = 0,
+        |metadata: &mut SaveMetadata| metadata.preview.height += 1,
+        |metadata: &mut SaveMetadata| _ = metadata.preview.pixels.pop(),
+    ] {
+        let mut broken = metadata.clone();
+        break_preview(&mut broken);
+        assert!(matches!(
+            SaveMetadata::from_block(&broken.to_block().unwrap()),
+            Err(LoadError::InvalidSave(_))
+        ));
+    }
+}
+
+#[test]
+fn save_failures_keep_previous_file() {
+    let manager = load(&fixture("world_v6.rsplnt")).unwrap();
+
+    let missing_directory = temp_save("missing").join("world.rsplnt");
+    assert!(matches!(
+        manager.save_world(&missing_directory),
+        Err(SaveError::FailedToWrite(_))
+    ));
+
+    // A directory can't be replaced by a save, and must survive the attempt
+    let directory = temp_save("directory.rsplnt");
+    fs::create_dir_all(&directory).unwrap();
+    assert!(matches!(
+        manager.save_world(&directory),
+        Err(SaveError::FailedToWrite(_))
+    ));
+    assert!(directory.is_dir());
+    let temporary_prefix = format!(".{}", directory.file_name().unwrap().to_string_lossy());
+    assert!(!fs::read_dir(std::env::temp_dir())
+        .unwrap()
+        .any(|entry| entry
+            .unwrap()
+            .file_name()
+            .to_string_lossy()
+            .starts_with(&temporary_prefix)));
+    fs::remove_dir(&directory).unwrap();
+}
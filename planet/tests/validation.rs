@@ -0,0 +1,106 @@
+use {
+    planet::{
+        world_manager::LoadError,
+        BiomeType,
+        World,
+        WorldManager,
+        WorldValidationError,
+    },
+    std::fs,
+};
+
+mod common;
+
+/// Saves the fixture after breaking it, and returns what loading it again
+/// reports
+fn load_broken(name: &str, break_world: impl FnOnce(&mut World)) -> WorldValidationError {
+    let mut manager = common::load_fixture();
+    break_world(manager.get_world_mut().unwrap());
+
+    let path = std::env::temp_dir().join(format!("planet-{}-{name}", std::process::id()));
+    manager.save_world(&path).unwrap();
+    let result = WorldManager::new().load_world(&path);
+    fs::remove_file(&path).unwrap();
+    match result {
+        Err(LoadError::InvalidWorld(err)) => err,
+        result => panic!("Broken world loaded as {result:?}"),
+    }
+}
+
+#[test]
+fn accepts_generated_worlds() {
+    let manager = common::load_fixture();
+    assert_eq!(manager.get_world().unwrap().validate(), Ok(()));
+}
+
+#[test]
+fn rejects_terrain_not_matching_size() {
+    assert_eq!(
+        load_broken("narrow.rsplnt", |world| world.width = 39),
+        WorldValidationError::WrongRowLength {
+            y:      0,
+            width:  39,
+            length: 40,
+        }
+    );
+    assert_eq!(
+        load_broken("low.rsplnt", |world| world.height = 19),
+        WorldValidationError::WrongRowCount {
+            height: 19,
+            rows:   20,
+        }
+    );
+}
+
+#[test]
+fn rejects_invalid_cells() {
+    assert!(matches!(
+        load_broken("nan.rsplnt", |world| world.terrain[1][2].altitude = f32::NAN),
+        WorldValidationError::NonFiniteValue {
+            x: 2,
+            y: 1,
+            field: "altitude",
+            ..
+        }
+    ));
+    assert_eq!(
+        load_broken("presences.rsplnt", |world| {
+            world.terrain[0][0].biome_presences = vec![(BiomeType::Ocean, 0.5)];
+        }),
+        WorldValidationError::InvalidBiomePresences {
+            x:     0,
+            y:     0,
+            total: 0.5,
+        }
+    );
+}
+
+#[test]
+fn rejects_inconsistent_json() {
+    let mut manager = common::load_fixture();
+    manager.get_world_mut().unwrap().terrain[5].truncate(10);
+
+    let path = std::env::temp_dir().join(format!("planet-{}-short.json", std::process::id()));
+    manager.save_json(&path).unwrap();
+    let result = WorldManager::new().load_json(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(
+        result,
+        Err(LoadError::InvalidWorld(
+            WorldValidationError::WrongRowLength { y: 5, .. }
+        ))
+    ));
+}
+
+#[test]
+fn rejects_invalid_resource_scarcity() {
+    assert_eq!(
+        load_broken("scarcity.rsplnt", |world| world.resource_scarcity = 0.0),
+        WorldValidationError::InvalidResourceScarcity(0.0)
+    );
+    assert!(matches!(
+        World::validate_resource_scarcity(f32::INFINITY),
+        Err(WorldValidationError::InvalidResourceScarcity(_))
+    ));
+    assert_eq!(World::validate_resource_scarcity(0.5), Ok(()));
+}
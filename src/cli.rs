@@ -0,0 +1,313 @@
+use {
+    planet::{
+        export::{ExportError, HeightmapFormat, MapImageOptions},
+        import::{Heightmap, HeightmapOptions},
+        map::{MapColors, WorldOverlay, WorldView},
+        palette::{BiomePalette, ColorMap, ColorRamp, Palette},
+        saving::WorldRecipe,
+        world_manager::WorldOrigin,
+        World,
+        WorldManager,
+    },
+    std::{error::Error, ffi::OsStr, fmt::Display, path::Path},
+};
+
+const USAGE: &str = "Usage:
+    worlds-history-sim-rs export-png <world.rsplnt|recipe.ron> <map.png> [options]
+        --view <view>            Biomes, Topography, Coastlines, Climate or Relief (default: Biomes)
+        --overlay <overlay>      Temperature, Rainfall, Arability, Resources, AltitudeContours,
+                                 RainfallContours or TemperatureContours, can be repeated
+        --scale <pixels>         Size of a cell in pixels (default: 1)
+        --shade-relief           Multiply the Relief view over the map
+        --colors <scale>=<map>   Colours of Temperature, Rainfall, Arability or Coastlines, one of
+                                 Classic, Viridis, Cividis, Magma, Inferno, Diverging, Greyscale
+                                 or a palette from --palettes, can be repeated
+        --palettes <file.ron>    Palette file with user-defined colour maps
+        --biome-colors <colors>  Classic or ColorBlindSafe (default: ColorBlindSafe)
+
+    worlds-history-sim-rs export-data <world.rsplnt|recipe.ron> <output name> [options]
+        --heightmap <format> Png or R16 (default: Png)
+
+    worlds-history-sim-rs import-heightmap <heightmap.png|.r16|.raw> <world.rsplnt> [options]
+        --size <width>x<height>  Size of the world, and of raw heightmaps (default: image size)
+        --seed <seed>            Seed for everything but the altitude (default: random)
+        --sea-level <sample>     Sample at altitude 0, from 0 to 1 (default: 0.5)
+        --altitude-scale <m>     Altitude between black and white (default: 30000)
+
+    worlds-history-sim-rs import-plnt <original.plnt> <world.rsplnt>";
+
+#[derive(Debug)]
+pub enum CliError {
+    MissingArgument(&'static str),
+    UnknownArgument(String),
+    InvalidValue(&'static str, String),
+}
+impl Error for CliError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+
+    fn description(&self) -> &str {
+        "description() is deprecated; use Display"
+    }
+
+    fn cause(&self) -> Option<&dyn Error> {
+        self.source()
+    }
+}
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CliError::MissingArgument(argument) => {
+                f.write_fmt(format_args!("Missing argument {argument}\n\n{USAGE}"))
+            },
+            CliError::UnknownArgument(argument) => {
+                f.write_fmt(format_args!("Unknown argument {argument}\n\n{USAGE}"))
+            },
+            CliError::InvalidValue(argument, value) => f.write_fmt(format_args!(
+                "Invalid value '{value}' for {argument}\n\n{USAGE}"
+            )),
+        }
+    }
+}
+
+/// Runs the command given on the command line, if any.
+/// Returns `None` when the game should start normally.
+pub fn run<I: Iterator<Item = String>>(mut args: I) -> Option<Result<(), Box<dyn Error>>> {
+    match args.next()?.as_str() {
+        "export-png" => Some(export_png(args)),
+        "export-data" => Some(export_data(args)),
+        "import-heightmap" => Some(import_heightmap(args)),
+        "import-plnt" => Some(import_plnt(args)),
+        "help" | "--help" | "-h" => {
+            println!("{USAGE}");
+            Some(Ok(()))
+        },
+        // Anything else is left to bevy and the windowing backend
+        _ => None,
+    }
+}
+
+/// Parses a variant of an iterable enum by its name, ignoring case
+fn parse_variant<T: Copy + Into<&'static str>>(
+    items: &'static [T],
+    argument: &'static str,
+    value: String,
+) -> Result<T, CliError> {
+    items
+        .iter()
+        .copied()
+        .find(|item| Into::<&'static str>::into(*item).eq_ignore_ascii_case(&value))
+        .ok_or(CliError::InvalidValue(argument, value))
+}
+
+/// Loads a save, or generates the world described by a recipe
+fn load_world(input: &str) -> Result<WorldManager, Box<dyn Error>> {
+    let mut world_manager = WorldManager::new();
+    if Path::new(input).extension() == Some(OsStr::new(WorldRecipe::EXTENSION)) {
+        let recipe = world_manager.load_recipe(input)?;
+        if !recipe.matches_generator() {
+            eprintln!(
+                "Recipe was written by generator version {}, the map may differ from the original",
+                recipe.generator_version
+            );
+        }
+        println!("Generating {}x{} world...", recipe.width, recipe.height);
+        let (progress_sender, _) = crossbeam_channel::bounded(0);
+        let mut world = recipe.world();
+        world.generate(&progress_sender)?;
+        world_manager.set_world(world, WorldOrigin::Generated);
+    } else {
+        world_manager.load_world(input)?;
+    }
+
+    Ok(world_manager)
+}
+
+fn export_png<I: Iterator<Item = String>>(mut args: I) -> Result<(), Box<dyn Error>> {
+    let input = args.next().ok_or(CliError::MissingArgument("<world>"))?;
+    let output = args.next().ok_or(CliError::MissingArgument("<map.png>"))?;
+
+    let mut options = MapImageOptions::default();
+    let mut ramps = Vec::new();
+    let mut palettes = Vec::new();
+    while let Some(argument) = args.next() {
+        let (name, value) = match argument.as_str() {
+            "--view" => ("--view", args.next()),
+            "--overlay" => ("--overlay", args.next()),
+            "--scale" => ("--scale", args.next()),
+            "--colors" => ("--colors", args.next()),
+            "--palettes" => ("--palettes", args.next()),
+            "--biome-colors" => ("--biome-colors", args.next()),
+            "--shade-relief" => {
+                options.style.hillshade.multiply = true;
+                continue;
+            },
+            _ => return Err(CliError::UnknownArgument(argument).into()),
+        };
+        let value = value.ok_or(CliError::MissingArgument(name))?;
+        match name {
+            "--view" => options.view = parse_variant(WorldView::ITEMS, name, value)?,
+            "--overlay" => {
+                _ = options
+                    .overlays
+                    .insert(parse_variant(WorldOverlay::ITEMS, name, value)?);
+            },
+            // Palettes may be given after the colours that use them
+            "--colors" => ramps.push(value),
+            "--palettes" => palettes.extend(Palette::load(&value)?),
+            "--biome-colors" => {
+                options.style.colors.biomes = parse_variant(BiomePalette::ITEMS, name, value)?;
+            },
+            _ => {
+                options.scale = match value.parse() {
+                    Ok(scale) if scale > 0 => scale,
+                    _ => return Err(CliError::InvalidValue(name, value).into()),
+                }
+            },
+        }
+    }
+
+    for value in ramps {
+        set_color_ramp(&mut options.style.colors, &palettes, value)?;
+    }
+
+    let world_manager = load_world(&input)?;
+    match world_manager.export_map_png(&output, &options) {
+        Ok(()) => {
+            let (width, height) = options.image_size(world_manager.get_world().unwrap())?;
+            println!("Exported {width}x{height} map to {output}");
+            Ok(())
+        },
+        Err(ExportError::FailedToWrite(err)) => {
+            Err(format!("Failed to write {output} - {err}").into())
+        },
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Sets the colours of a scale from a `--colors <scale>=<map>` value
+fn set_color_ramp(
+    colors: &mut MapColors,
+    palettes: &[Palette],
+    value: String,
+) -> Result<(), CliError> {
+    let Some((scale, map)) = value.split_once('=') else {
+        return Err(CliError::InvalidValue("--colors", value));
+    };
+    let ramp = match scale.to_ascii_lowercase().as_str() {
+        "temperature" => &mut colors.temperature,
+        "rainfall" => &mut colors.rainfall,
+        "arability" => &mut colors.arability,
+        "coastlines" => &mut colors.coastlines,
+        _ => return Err(CliError::InvalidValue("--colors", value)),
+    };
+    *ramp = if map.eq_ignore_ascii_case("Classic") {
+        ColorRamp::Classic
+    } else if let Some(palette) = palettes.iter().find(|palette| palette.name == map) {
+        ColorRamp::Custom(palette.clone())
+    } else {
+        ColorRamp::Map(parse_variant(ColorMap::ITEMS, "--colors", map.to_string())?)
+    };
+    Ok(())
+}
+
+fn export_data<I: Iterator<Item = String>>(mut args: I) -> Result<(), Box<dyn Error>> {
+    let input = args.next().ok_or(CliError::MissingArgument("<world>"))?;
+    let output = args
+        .next()
+        .ok_or(CliError::MissingArgument("<output name>"))?;
+
+    let mut heightmap_format = HeightmapFormat::Png;
+    while let Some(argument) = args.next() {
+        match argument.as_str() {
+            "--heightmap" => {
+                let value = args
+                    .next()
+                    .ok_or(CliError::MissingArgument("--heightmap"))?;
+                heightmap_format = parse_variant(HeightmapFormat::ITEMS, "--heightmap", value)?;
+            },
+            _ => return Err(CliError::UnknownArgument(argument).into()),
+        }
+    }
+
+    let world_manager = load_world(&input)?;
+    let metadata = world_manager.export_data(&output, heightmap_format)?;
+    for layer in metadata.layers {
+        println!("Exported {:?} to {}", layer.layer, layer.file);
+    }
+    Ok(())
+}
+
+fn import_heightmap<I: Iterator<Item = String>>(mut args: I) -> Result<(), Box<dyn Error>> {
+    let input = args
+        .next()
+        .ok_or(CliError::MissingArgument("<heightmap>"))?;
+    let output = args
+        .next()
+        .ok_or(CliError::MissingArgument("<world.rsplnt>"))?;
+
+    let mut size = None;
+    let mut seed = None;
+    let mut options = HeightmapOptions::default();
+    while let Some(argument) = args.next() {
+        let name = match argument.as_str() {
+            "--size" => "--size",
+            "--seed" => "--seed",
+            "--sea-level" => "--sea-level",
+            "--altitude-scale" => "--altitude-scale",
+            _ => return Err(CliError::UnknownArgument(argument).into()),
+        };
+        let value = args.next().ok_or(CliError::MissingArgument(name))?;
+        let invalid = || CliError::InvalidValue(name, value.clone());
+        match name {
+            "--size" => {
+                let (width, height) = value.split_once('x').ok_or_else(invalid)?;
+                size = match (width.parse(), height.parse()) {
+                    (Ok(width), Ok(height)) if width > 0 && height > 0 => Some((width, height)),
+                    _ => return Err(invalid().into()),
+                };
+            },
+            "--seed" => seed = Some(value.parse().map_err(|_| invalid())?),
+            "--sea-level" => options.sea_level = value.parse().map_err(|_| invalid())?,
+            _ => options.altitude_scale = value.parse().map_err(|_| invalid())?,
+        }
+    }
+
+    let (raw_width, raw_height) = size.unwrap_or_default();
+    let heightmap = Heightmap::open(&input, raw_width, raw_height)?;
+    let (width, height) = size.unwrap_or((heightmap.width, heightmap.height));
+
+    println!(
+        "Generating {width}x{height} world from {}x{} heightmap...",
+        heightmap.width, heightmap.height
+    );
+    let (progress_sender, _) = crossbeam_channel::bounded(0);
+    let mut world = World::new(width, height, seed.unwrap_or_else(rand::random));
+    world.generate_from_heightmap(&heightmap, &options, &progress_sender)?;
+
+    let mut world_manager = WorldManager::new();
+    world_manager.set_world(world, WorldOrigin::Heightmap);
+    let report = world_manager.save_world(&output)?;
+    println!("Saved {output} ({report})");
+    Ok(())
+}
+
+fn import_plnt<I: Iterator<Item = String>>(mut args: I) -> Result<(), Box<dyn Error>> {
+    let input = args
+        .next()
+        .ok_or(CliError::MissingArgument("<original.plnt>"))?;
+    let output = args
+        .next()
+        .ok_or(CliError::MissingArgument("<world.rsplnt>"))?;
+    if let Some(argument) = args.next() {
+        return Err(CliError::UnknownArgument(argument).into());
+    }
+
+    let mut world_manager = WorldManager::new();
+    let report = world_manager.import_plnt(&input)?;
+    print!("{report}");
+    let report = world_manager.save_world(&output)?;
+    println!("Saved {output} ({report})");
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use {
+    crate::{
+        gui::{WidgetId, WidgetSystem},
+        planet_renderer::WorldRenderSettings,
+    },
+    bevy::{
+        ecs::{
+            prelude::Res,
+            system::{Local, SystemParam, SystemState},
+            world::World,
+        },
+        utils::HashSet,
+    },
+    bevy_egui::egui::{vec2, Align2, Color32, ScrollArea, Sense, Stroke, TextStyle, Ui},
+    planet::{
+        color::Rgb,
+        legend::{self, LegendContent, LegendSection, LegendTick},
+        map::{MapStyle, WorldOverlay, WorldView},
+        WorldManager,
+    },
+};
+
+/// Width of the colour ramps, in points
+const RAMP_WIDTH: f32 = 200.0;
+const SWATCH_SIZE: [f32; 2] = [16.0, 12.0];
+
+/// Explains the colours of the map. The legend is only rebuilt when the
+/// render settings or the world change.
+#[derive(SystemParam)]
+pub struct LegendWidget<'w, 's> {
+    render_settings: Res<'w, WorldRenderSettings>,
+    world_manager:   Res<'w, WorldManager>,
+    sections:        Local<'s, Vec<LegendSection>>,
+    /// What the legend was last built from
+    built_from:      Local<'s, Option<LegendInputs>>,
+}
+
+#[derive(PartialEq)]
+pub struct LegendInputs {
+    world_revision: u64,
+    view:           WorldView,
+    overlays:       HashSet<WorldOverlay>,
+    style:          MapStyle,
+}
+impl WidgetSystem for LegendWidget<'_, '_> {
+    fn render(world: &mut World, state: &mut SystemState<Self>, ui: &mut Ui, _id: WidgetId) {
+        let mut state = state.get_mut(world);
+        let Some(planet) = state.world_manager.get_world() else {
+            return;
+        };
+        // Both resources are borrowed mutably every frame, so change detection
+        // can't tell whether the legend is out of date
+        let inputs = LegendInputs {
+            world_revision: state.world_manager.world_revision(),
+            view:           state.render_settings.view,
+            overlays:       state.render_settings.visible_overlays().clone(),
+            style:          state.render_settings.style.clone(),
+        };
+        if state.built_from.as_ref() != Some(&inputs) {
+            *state.sections = legend::legend(planet, inputs.view, &inputs.overlays, &inputs.style);
+            *state.built_from = Some(inputs);
+        }
+
+        _ = ScrollArea::vertical().show(ui, |ui| {
+            for section in state.sections.iter() {
+                _ = ui.strong(section.title);
+                match &section.content {
+                    LegendContent::Swatches(swatches) => {
+                        for (label, color) in swatches {
+                            ui.horizontal(|ui| {
+                                let (rect, _) =
+                                    ui.allocate_exact_size(SWATCH_SIZE.into(), Sense::hover());
+                                ui.painter().rect_filled(rect, 2.0, color32(*color));
+                                ui.label(label);
+                            });
+                        }
+                    },
+                    LegendContent::Ramp { colors, ticks } => ramp(ui, colors, ticks),
+                    LegendContent::Contour { color, label } => {
+                        ui.horizontal(|ui| {
+                            let (rect, _) =
+                                ui.allocate_exact_size(SWATCH_SIZE.into(), Sense::hover());
+                            ui.painter().line_segment(
+                                [rect.left_center(), rect.right_center()],
+                                Stroke::new(2.0, color32(*color)),
+                            );
+                            ui.label(label);
+                        });
+                    },
+                }
+                ui.add_space(8.0);
+            }
+        });
+    }
+}
+
+fn ramp(ui: &mut Ui, colors: &[Rgb], ticks: &[LegendTick]) {
+    let (rect, _) = ui.allocate_exact_size(vec2(RAMP_WIDTH, 16.0), Sense::hover());
+    let step = rect.width() / colors.len() as f32;
+    for (index, color) in colors.iter().enumerate() {
+        let mut slice = rect;
+        slice.set_left(rect.left() + step * index as f32);
+        slice.set_width(step + 0.5);
+        ui.painter().rect_filled(slice, 0.0, color32(*color));
+    }
+
+    let font = TextStyle::Small.resolve(ui.style());
+    let (labels, _) = ui.allocate_exact_size(vec2(RAMP_WIDTH, font.size + 6.0), Sense::hover());
+    let text_color = ui.visuals().text_color();
+    for tick in ticks {
+        let x = rect.left() + tick.position * rect.width();
+        ui.painter().line_segment(
+            [
+                [x, rect.bottom() - 4.0].into(),
+                [x, rect.bottom() + 2.0].into(),
+            ],
+            Stroke::new(1.0, text_color),
+        );
+        // Labels at the ends are kept inside the ramp
+        let anchor = if tick.position <= 0.0 {
+            Align2::LEFT_TOP
+        } else if tick.position >= 1.0 {
+            Align2::RIGHT_TOP
+        } else {
+            Align2::CENTER_TOP
+        };
+        _ = ui.painter().text(
+            [x, labels.top() + 2.0].into(),
+            anchor,
+            &tick.label,
+            font.clone(),
+            text_color,
+        );
+    }
+}
+
+fn color32(color: Rgb) -> Color32 {
+    let [red, green, blue] = color.as_rgb_u8();
+    Color32::from_rgb(red, green, blue)
+}
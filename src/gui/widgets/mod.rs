@@ -1,2 +1,4 @@
+mod legend;
+pub use legend::LegendWidget;
 mod toolbar;
 pub use toolbar::ToolbarWidget;
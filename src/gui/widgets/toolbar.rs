@@ -17,11 +17,14 @@ use {
         },
         log::debug,
     },
-    bevy_egui::egui::{Layout, Ui},
+    bevy_egui::egui::{DragValue, Layout, Ui},
     planet::WorldManager,
-    std::marker::PhantomData,
+    std::{marker::PhantomData, ops::RangeInclusive},
 };
 
+/// Resource scarcities that can be picked for new worlds
+const RESOURCE_SCARCITY_RANGE: RangeInclusive<f32> = 0.1..=10.0;
+
 iterable_enum!(ToolbarButton {
     GenerateWorld,
     SaveLoad,
@@ -104,6 +107,20 @@ impl WidgetSystem for ToolbarWidget<'_, '_> {
                         button.clicked(world);
                     }
                 }
+
+                let mut world_manager = world.resource_mut::<WorldManager>();
+                let mut options = world_manager.new_world_options();
+                ui.label("Resource scarcity");
+                _ = ui
+                    .add(
+                        DragValue::new(&mut options.resource_scarcity)
+                            .clamp_range(RESOURCE_SCARCITY_RANGE)
+                            .speed(0.01),
+                    )
+                    .on_hover_text("Higher values make resources rarer in newly generated worlds");
+                if options != world_manager.new_world_options() {
+                    world_manager.set_new_world_options(options);
+                }
             },
         );
     }
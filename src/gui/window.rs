@@ -30,6 +30,7 @@ pub fn render_windows(world: &mut World, ctx: &Context) {
     window::<windows::WorldViewSelection>(world, ctx);
     window::<windows::WorldOverlaySelection>(world, ctx);
     window::<windows::SaveLoad>(world, ctx);
+    window::<windows::RestoreAutosave>(world, ctx);
 }
 
 pub fn open_window<S: 'static + WindowSystem>(windows: &mut OpenedWindows) {
@@ -6,3 +6,5 @@ mod world_overlay_selection;
 pub use world_overlay_selection::WorldOverlaySelection;
 mod save_load;
 pub use save_load::SaveLoad;
+mod restore_autosave;
+pub use restore_autosave::RestoreAutosave;
@@ -0,0 +1,108 @@
+use {
+    crate::{
+        gui::{close_window, WindowSystem},
+        plugins::world_asset_plugin::load_world,
+        resources::{Autosave, LoadWorldTask, OpenedWindows, PeekSaveTask},
+    },
+    bevy::{
+        asset::AssetServer,
+        ecs::{
+            change_detection::Mut,
+            system::{Local, SystemParam, SystemState},
+            world::World,
+        },
+        log::error,
+        tasks::AsyncComputeTaskPool,
+    },
+    bevy_egui::egui::Ui,
+    futures_lite::future::{block_on, poll_once},
+    planet::{saving::SaveMetadata, WorldManager},
+    std::marker::PhantomData,
+};
+
+#[derive(SystemParam)]
+pub struct RestoreAutosave<'w, 's> {
+    /// `None` until peeked, `Some(Err(_))` with the reason the autosave can't
+    /// be read
+    pub metadata:  Local<'s, Option<Result<Option<SaveMetadata>, String>>>,
+    pub peek_task: Local<'s, Option<PeekSaveTask>>,
+    #[system_param(ignore)]
+    _phantom:      PhantomData<(&'w (), &'s ())>,
+}
+
+impl WindowSystem for RestoreAutosave<'_, '_> {
+    fn draw_contents(world: &mut World, state: &mut SystemState<Self>, ui: &mut Ui) {
+        world.resource_scope(|world, mut autosave: Mut<Autosave>| {
+            let Some(path) = autosave.restorable.clone() else {
+                close_window::<Self>(&mut world.resource_mut::<OpenedWindows>());
+                return;
+            };
+            let mut state = state.get_mut(world);
+
+            ui.label("The last session didn't exit cleanly. Restore its newest autosave?");
+            if state.metadata.is_none() {
+                let task = state.peek_task.get_or_insert_with(|| {
+                    let path = path.clone();
+                    AsyncComputeTaskPool::get().spawn(async move { WorldManager::peek_save(path) })
+                });
+                if task.is_finished() {
+                    *state.metadata = block_on(poll_once(task)).map(|result| {
+                        result.map_err(|err| {
+                            error!("Failed to read autosave: {err}");
+                            err.to_string()
+                        })
+                    });
+                    *state.peek_task = None;
+                }
+            }
+            match &*state.metadata {
+                None => {
+                    ui.label("Reading the autosave...");
+                },
+                Some(Ok(Some(metadata))) => {
+                    ui.label(format!(
+                        "{}x{}, seed {}, iteration {}",
+                        metadata.width, metadata.height, metadata.seed, metadata.iteration
+                    ));
+                    if !metadata.info.title.is_empty() {
+                        ui.label(metadata.info.title.as_str());
+                    }
+                },
+                Some(Ok(None)) => {
+                    ui.label("The autosave has no description.");
+                },
+                Some(Err(err)) => {
+                    ui.label(format!("The autosave can't be read: {err}"));
+                },
+            }
+
+            let mut answered = false;
+            ui.horizontal(|ui| {
+                if ui.button("Restore").clicked() {
+                    answered = true;
+                    let asset_server = world.resource::<AssetServer>().clone();
+                    load_world(
+                        &asset_server,
+                        &mut world.resource_mut::<LoadWorldTask>(),
+                        &path,
+                    );
+                }
+                if ui.button("Discard").clicked() {
+                    answered = true;
+                }
+            });
+            if answered {
+                autosave.restorable = None;
+                close_window::<Self>(&mut world.resource_mut::<OpenedWindows>());
+            }
+        });
+    }
+
+    fn name() -> &'static str {
+        "Restore autosave"
+    }
+
+    fn resizable() -> bool {
+        false
+    }
+}
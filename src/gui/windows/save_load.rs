@@ -1,23 +1,65 @@
 use {
-    crate::{gui::WindowSystem, resources::ShouldRedraw},
+    crate::{
+        gui::WindowSystem,
+        plugins::world_asset_plugin::load_world,
+        resources::{
+            Autosave,
+            GenerateWorldProgressChannel,
+            GenerateWorldTask,
+            LoadWorldTask,
+            PeekSaveTask,
+            ShouldRedraw,
+        },
+    },
     bevy::{
+        asset::AssetServer,
         ecs::{
             change_detection::Mut,
             system::{Local, SystemParam, SystemState},
             world::World,
         },
-        log::error,
+        log::{debug, error},
+        tasks::AsyncComputeTaskPool,
+    },
+    bevy_egui::egui::{
+        Color32,
+        ColorImage,
+        ComboBox,
+        DragValue,
+        Response,
+        TextureFilter,
+        TextureHandle,
+        Ui,
+    },
+    futures_lite::future::{block_on, poll_once},
+    planet::{
+        import::plnt,
+        saving::{
+            recipe::GENERATOR_VERSION,
+            AutosaveSettings,
+            SaveCompression,
+            SaveFormat,
+            SaveMetadata,
+        },
+        WorldManager,
     },
-    bevy_egui::egui::Ui,
-    planet::WorldManager,
-    std::marker::PhantomData,
+    std::{ffi::OsStr, marker::PhantomData, path::Path},
 };
 
+/// Width of the preview of a save picked for loading, in points
+const PREVIEW_WIDTH: f32 = 256.0;
+
 #[derive(SystemParam)]
 pub struct SaveLoad<'w, 's> {
-    pub file_name: Local<'s, String>,
+    pub file_name:   Local<'s, String>,
+    pub last_report: Local<'s, String>,
+    /// Save picked in the Load dialog, previewed until it's opened
+    pub peeked_save: Local<'s, Option<(String, SaveMetadata)>>,
+    /// Save picked in the Load dialog while its metadata is read
+    pub peek_task:   Local<'s, Option<(String, PeekSaveTask)>>,
+    pub preview:     Local<'s, Option<TextureHandle>>,
     #[system_param(ignore)]
-    _phantom:      PhantomData<(&'w (), &'s ())>,
+    _phantom:        PhantomData<(&'w (), &'s ())>,
 }
 
 impl WindowSystem for SaveLoad<'_, '_> {
@@ -26,37 +68,240 @@ impl WindowSystem for SaveLoad<'_, '_> {
             world.resource_scope(|world, mut should_redraw: Mut<ShouldRedraw>| {
                 let mut state = state.get_mut(world);
 
+                if world_manager.get_world().is_some() {
+                    let info = world_manager.info_mut();
+                    ui.horizontal(|ui| {
+                        ui.label("Title");
+                        ui.text_edit_singleline(&mut info.title);
+                    });
+                    ui.label("Description");
+                    ui.text_edit_multiline(&mut info.description);
+                    ui.label(format!("Created {}", info.created_utc()));
+                    ui.separator();
+                }
+
+                let mut save_options = world_manager.save_options();
+                ComboBox::from_label("Compression")
+                    .selected_text(<&'static str>::from(save_options.compression))
+                    .show_ui(ui, |ui| {
+                        for compression in SaveCompression::iterator() {
+                            _ = ui.selectable_value(
+                                &mut save_options.compression,
+                                *compression,
+                                <&'static str>::from(compression),
+                            );
+                        }
+                    });
+                _ = ui
+                    .checkbox(&mut save_options.quantize, "Quantize terrain")
+                    .on_hover_text(
+                        "Store altitude, rainfall and temperature with less precision to make \
+                         saves smaller",
+                    );
+                world_manager.set_save_options(save_options);
+
+                let mut loaded_recipe = None;
                 ui.horizontal(|ui| {
                     if ui.button("Save").clicked() {
+                        // Recipes would regenerate other worlds as a different one
+                        let can_save_recipe = world_manager.can_save_recipe();
+                        let filters: Vec<String> = SaveFormat::iterator()
+                            .filter(|format| can_save_recipe || **format != SaveFormat::Recipe)
+                            .map(|format| format.filter())
+                            .collect();
+                        let filters: Vec<&str> = filters.iter().map(String::as_str).collect();
                         if let Some(path) = tinyfiledialogs::save_file_dialog_with_filter(
                             "Save world",
                             state.file_name.as_str(),
-                            &["*.rsplnt", "*.rsplnt"],
-                            "World file",
+                            &filters,
+                            if can_save_recipe {
+                                "World file, recipe, JSON world or CSV terrain table"
+                            } else {
+                                "World file, JSON world or CSV terrain table"
+                            },
                         ) {
-                            if let Err(err) = world_manager.save_world(&path) {
-                                // TODO: Error popup
-                                error!("Failed to save: {err}");
+                            let saved = match SaveFormat::from_path(&path) {
+                                SaveFormat::Binary => world_manager
+                                    .save_world(&path)
+                                    .map(|report| format!("Saved {report}")),
+                                SaveFormat::Recipe => world_manager
+                                    .save_recipe(&path)
+                                    .map(|()| String::from("Saved recipe")),
+                                SaveFormat::Json => world_manager
+                                    .save_json(&path)
+                                    .map(|()| String::from("Saved JSON world")),
+                                SaveFormat::Csv => world_manager
+                                    .save_csv(&path)
+                                    .map(|()| String::from("Saved terrain table")),
+                            };
+                            match saved {
+                                Ok(report) => *state.last_report = report,
+                                Err(err) => {
+                                    // TODO: Error popup
+                                    error!("Failed to save: {err}");
+                                },
                             }
                             *state.file_name = path;
                         }
                     }
                     if ui.button("Load").clicked() {
+                        let filters: Vec<String> = SaveFormat::iterator()
+                            .filter(|format| format.can_load())
+                            .map(|format| format.filter())
+                            .chain([format!("*.{}", plnt::EXTENSION)])
+                            .collect();
+                        let filters: Vec<&str> = filters.iter().map(String::as_str).collect();
                         if let Some(path) = tinyfiledialogs::open_file_dialog(
                             "World file",
                             state.file_name.as_str(),
-                            Some((&["*.rsplnt"], "*.rspnt")),
+                            Some((
+                                &filters,
+                                "World file, recipe, JSON world or original Worlds History Sim \
+                                 save",
+                            )),
                         ) {
-                            if let Err(err) = world_manager.load_world(&path) {
-                                // TODO: Error popup
-                                error!("Failed to load: {err}");
+                            if has_extension(&path, plnt::EXTENSION) {
+                                match world_manager.import_plnt(&path) {
+                                    Ok(report) => {
+                                        *state.last_report = if report.is_complete() {
+                                            String::from("Imported world")
+                                        } else {
+                                            format!("Imported world\n{report}")
+                                        };
+                                        should_redraw.0 = true;
+                                    },
+                                    Err(err) => error!("Failed to import: {err}"),
+                                }
+                            } else if SaveFormat::from_path(&path) == SaveFormat::Recipe {
+                                match world_manager.load_recipe(&path) {
+                                    Ok(recipe) => {
+                                        *state.last_report = if recipe.matches_generator() {
+                                            String::new()
+                                        } else {
+                                            format!(
+                                                "Recipe was made with generator version {}, this \
+                                                 is version {GENERATOR_VERSION}. The world may \
+                                                 differ from the original.",
+                                                recipe.generator_version
+                                            )
+                                        };
+                                        loaded_recipe = Some(recipe);
+                                    },
+                                    Err(err) => error!("Failed to load recipe: {err}"),
+                                }
+                            } else if SaveFormat::from_path(&path) == SaveFormat::Json {
+                                if let Err(err) = world_manager.load_json(&path) {
+                                    // TODO: Error popup
+                                    error!("Failed to load: {err}");
+                                } else {
+                                    should_redraw.0 = true;
+                                }
                             } else {
-                                should_redraw.0 = true;
+                                let peeked_path = path.clone();
+                                let task = AsyncComputeTaskPool::get()
+                                    .spawn(async move { WorldManager::peek_save(peeked_path) });
+                                *state.peek_task = Some((path.clone(), task));
+                                *state.peeked_save = None;
+                                *state.preview = None;
                             }
                             *state.file_name = path;
                         }
                     }
                 });
+                if !state.last_report.is_empty() {
+                    ui.label(state.last_report.as_str());
+                }
+
+                let mut load_path = None;
+                let peeked = matches!(&*state.peek_task, Some((_, task)) if task.is_finished());
+                if peeked {
+                    let (path, mut task) = state.peek_task.take().unwrap();
+                    match block_on(poll_once(&mut task)) {
+                        Some(Ok(Some(metadata))) => *state.peeked_save = Some((path, metadata)),
+                        // Saves without metadata have nothing to preview
+                        Some(Ok(None)) => load_path = Some(path),
+                        Some(Err(err)) => {
+                            error!("Failed to load: {err}");
+                            *state.last_report = format!("Failed to read save: {err}");
+                        },
+                        None => {},
+                    }
+                } else if state.peek_task.is_some() {
+                    ui.label("Reading save...");
+                }
+
+                let mut open_peeked_save = None;
+                if let Some((_, metadata)) = &*state.peeked_save {
+                    ui.separator();
+                    let preview = state.preview.get_or_insert_with(|| {
+                        let preview = &metadata.preview;
+                        let image = ColorImage {
+                            size:   [preview.width as usize, preview.height as usize],
+                            pixels: preview
+                                .pixels
+                                .chunks_exact(3)
+                                .map(|pixel| Color32::from_rgb(pixel[0], pixel[1], pixel[2]))
+                                .collect(),
+                        };
+                        ui.ctx()
+                            .load_texture("Save preview", image, TextureFilter::Nearest)
+                    });
+                    let scale = PREVIEW_WIDTH / metadata.preview.width as f32;
+                    ui.image(preview.id(), preview.size_vec2() * scale);
+
+                    let info = &metadata.info;
+                    ui.heading(if info.title.is_empty() {
+                        "Untitled world"
+                    } else {
+                        info.title.as_str()
+                    });
+                    if !info.description.is_empty() {
+                        ui.label(info.description.as_str());
+                    }
+                    ui.label(format!(
+                        "{}x{}, seed {}, iteration {}",
+                        metadata.width, metadata.height, metadata.seed, metadata.iteration
+                    ));
+                    ui.label(format!("Created {}", info.created_utc()));
+                    ui.horizontal(|ui| {
+                        if ui.button("Open").clicked() {
+                            open_peeked_save = Some(true);
+                        }
+                        if ui.button("Cancel").clicked() {
+                            open_peeked_save = Some(false);
+                        }
+                    });
+                }
+                if let Some(open) = open_peeked_save {
+                    let peeked_save = state.peeked_save.take();
+                    *state.preview = None;
+                    if let (true, Some((path, _))) = (open, peeked_save) {
+                        load_path = Some(path);
+                    }
+                }
+                if let Some(path) = load_path {
+                    let asset_server = world.resource::<AssetServer>().clone();
+                    load_world(
+                        &asset_server,
+                        &mut world.resource_mut::<LoadWorldTask>(),
+                        Path::new(&path),
+                    );
+                }
+
+                ui.separator();
+                autosave_settings(&mut world.resource_mut::<Autosave>(), ui);
+
+                if let Some(recipe) = loaded_recipe {
+                    let progress_sender = world.resource::<GenerateWorldProgressChannel>().sender();
+                    let mut generate_world_task = world.resource_mut::<GenerateWorldTask>();
+                    if generate_world_task.0.is_some() {
+                        debug!("Already generating new world");
+                    } else {
+                        generate_world_task.0 = Some(
+                            world_manager.new_world_from_recipe_async(recipe, progress_sender),
+                        );
+                    }
+                }
             });
         });
     }
@@ -69,3 +314,61 @@ impl WindowSystem for SaveLoad<'_, '_> {
         true
     }
 }
+
+fn autosave_settings(autosave: &mut Autosave, ui: &mut Ui) {
+    let Some(autosaves) = &autosave.autosaves else {
+        ui.label("Autosave is unavailable without a user data directory");
+        return;
+    };
+
+    let mut settings = autosave.settings;
+    let settled = ui
+        .horizontal(|ui| {
+            let enabled = ui.checkbox(&mut settings.enabled, "Autosave every");
+            let interval = ui.add_enabled(
+                settings.enabled,
+                DragValue::new(&mut settings.interval_minutes)
+                    .clamp_range(1..=120)
+                    .suffix(" min"),
+            );
+            enabled.changed() || is_settled(&interval)
+        })
+        .inner;
+    let slots = ui.horizontal(|ui| {
+        ui.label("Keep");
+        ui.add_enabled(
+            settings.enabled,
+            DragValue::new(&mut settings.slots)
+                .clamp_range(1..=AutosaveSettings::MAX_SLOTS)
+                .suffix(" autosaves"),
+        )
+    });
+    let settled = settled || is_settled(&slots.inner);
+    _ = slots.response.on_hover_text(format!(
+        "Autosaves are kept in {}",
+        autosaves.directory().display()
+    ));
+
+    // Dragging changes the settings every frame, so they are only written once
+    // the value is let go of
+    if settled {
+        if let Err(err) = autosaves.save_settings(&settings) {
+            error!("Failed to save autosave settings: {err}");
+        }
+    }
+    if settings != autosave.settings {
+        if settings.interval_minutes != autosave.settings.interval_minutes {
+            autosave.timer = Autosave::interval_timer(&settings);
+        }
+        autosave.settings = settings;
+    }
+}
+
+/// Whether a `DragValue` is done being dragged or typed into
+fn is_settled(response: &Response) -> bool {
+    response.drag_released() || response.lost_focus()
+}
+
+fn has_extension(path: &str, extension: &str) -> bool {
+    Path::new(path).extension() == Some(OsStr::new(extension))
+}
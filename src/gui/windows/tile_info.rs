@@ -5,7 +5,7 @@ use {
         world::World,
     },
     bevy_egui::egui::{Grid, Ui},
-    planet::{BiomeStats, TerrainCell, WorldManager},
+    planet::{TerrainCell, WorldManager},
     std::marker::PhantomData,
 };
 
@@ -34,15 +34,19 @@ impl WindowSystem for TileInfo<'_, '_> {
                     && cursor_y >= 0
                     && cursor_y < world.height.try_into().unwrap()
                 {
+                    let cell = &world.terrain[cursor_y as usize][cursor_x as usize];
                     let TerrainCell {
                         altitude,
                         rainfall,
                         temperature,
                         biome_presences,
+                        soil_fertility,
+                        arability,
+                        resources,
                         x,
                         y,
                         ..
-                    } = &world.terrain[cursor_y as usize][cursor_x as usize];
+                    } = cell;
 
                     _ = ui.label("Coordinates");
                     _ = ui.label(format!("{x}:{y}"));
@@ -55,15 +59,41 @@ impl WindowSystem for TileInfo<'_, '_> {
                     ui.end_row();
                     _ = ui.label("Temperature");
                     _ = ui.label(format!("{temperature:.2}"));
+                    ui.end_row();
+                    _ = ui.label("Climate");
+                    _ = ui.label(match world.koppen_class(cell) {
+                        Some(koppen_class) => {
+                            format!("{} ({})", koppen_class.code(), koppen_class.description())
+                        },
+                        None => String::from("Ocean"),
+                    });
+                    ui.end_row();
+                    _ = ui.label("Soil fertility");
+                    _ = ui.label(format!("{:.2}%", soil_fertility * 100.0));
+                    ui.end_row();
+                    _ = ui.label("Arability");
+                    _ = ui.label(format!("{:.2}%", arability * 100.0));
 
                     ui.end_row();
                     ui.end_row();
                     _ = ui.label("Biome presences");
                     for (biome_type, presence) in biome_presences {
                         ui.end_row();
-                        _ = ui.label(<BiomeStats>::from(biome_type).name);
+                        _ = ui.label(biome_type.stats().name);
                         _ = ui.label(format!("{:.2}%", presence * 100.0));
                     }
+
+                    ui.end_row();
+                    ui.end_row();
+                    _ = ui.label("Resources");
+                    if resources.is_empty() {
+                        _ = ui.label("None");
+                    }
+                    for (resource_type, amount) in resources {
+                        ui.end_row();
+                        _ = ui.label(resource_type.stats().name);
+                        _ = ui.label(format!("{:.2}%", amount * 100.0));
+                    }
                 } else {
                     _ = ui.label("No tile at this position");
                 }
@@ -1,16 +1,23 @@
 use {
     crate::{
         gui::WindowSystem,
-        planet_renderer::{WorldOverlay, WorldRenderSettings},
-        resources::ShouldRedraw,
+        planet_renderer::WorldRenderSettings,
+        resources::{ShouldRedraw, UserPalettes},
     },
-    bevy::ecs::{
-        change_detection::Mut,
-        system::{SystemParam, SystemState},
-        world::World,
+    bevy::{
+        ecs::{
+            change_detection::Mut,
+            system::{SystemParam, SystemState},
+            world::World,
+        },
+        log::error,
     },
-    bevy_egui::egui::Ui,
-    std::marker::PhantomData,
+    bevy_egui::egui::{ComboBox, DragValue, Ui},
+    planet::{
+        map::{ContourIntervals, WorldOverlay},
+        palette::{ColorMap, ColorRamp, Palette},
+    },
+    std::{marker::PhantomData, ops::RangeInclusive},
 };
 
 #[derive(SystemParam)]
@@ -22,18 +29,54 @@ pub struct WorldOverlaySelection<'w, 's> {
 impl WindowSystem for WorldOverlaySelection<'_, '_> {
     fn draw_contents(world: &mut World, _state: &mut SystemState<Self>, ui: &mut Ui) {
         world.resource_scope(|world, mut render_settings: Mut<WorldRenderSettings>| {
+            let mut redraw = false;
+            let palettes = &world.resource::<UserPalettes>().0;
             for overlay in WorldOverlay::iterator() {
-                if ui
-                    .selectable_label(
-                        render_settings.overlay_visible(overlay),
-                        <&'static str>::from(overlay),
-                    )
-                    .clicked()
-                {
-                    render_settings.toggle_overlay(overlay);
-                    world.resource_mut::<ShouldRedraw>().0 = true;
+                ui.horizontal(|ui| {
+                    if ui
+                        .selectable_label(
+                            render_settings.overlay_visible(overlay),
+                            overlay.label(),
+                        )
+                        .clicked()
+                    {
+                        render_settings.toggle_overlay(overlay);
+                        redraw = true;
+                    }
+                    if let Some((interval, range, unit)) =
+                        contour_interval(&mut render_settings.style.contours, *overlay)
+                    {
+                        redraw |= ui
+                            .add(DragValue::new(interval).clamp_range(range).suffix(unit))
+                            .on_hover_text("Interval between lines")
+                            .changed();
+                    }
+                    if let Some(ramp) = render_settings.style.colors.overlay_ramp_mut(*overlay) {
+                        redraw |= color_ramp_selection(ui, overlay.into(), ramp, palettes);
+                    }
+                });
+            }
+
+            ui.separator();
+            if ui
+                .button("Load palettes")
+                .on_hover_text("Add the palettes of a palette file to the colour maps")
+                .clicked()
+            {
+                if let Some(path) = tinyfiledialogs::open_file_dialog(
+                    "Palette file",
+                    Palette::FILE_NAME,
+                    Some((&[&format!("*.{}", Palette::EXTENSION)], "Palette file")),
+                ) {
+                    match Palette::load(&path) {
+                        Ok(palettes) => world.resource_mut::<UserPalettes>().0 = palettes,
+                        Err(err) => error!("Failed to load palettes: {err}"),
+                    }
                 }
             }
+            if redraw {
+                world.resource_mut::<ShouldRedraw>().0 = true;
+            }
         });
     }
 
@@ -45,3 +88,45 @@ impl WindowSystem for WorldOverlaySelection<'_, '_> {
         false
     }
 }
+
+/// Interval of a contour overlay, with the range it can be set to and its unit
+fn contour_interval(
+    contours: &mut ContourIntervals,
+    overlay: WorldOverlay,
+) -> Option<(&mut f32, RangeInclusive<f32>, &'static str)> {
+    match overlay {
+        WorldOverlay::AltitudeContours => Some((&mut contours.altitude, 50.0..=5000.0, " m")),
+        WorldOverlay::RainfallContours => Some((&mut contours.rainfall, 25.0..=2000.0, " mm")),
+        WorldOverlay::TemperatureContours => Some((&mut contours.temperature, 1.0..=20.0, " °C")),
+        _ => None,
+    }
+}
+
+/// Lets the classic colours, a named colour map or one of the user's palettes
+/// be picked. Returns whether the pick changed.
+pub(super) fn color_ramp_selection(
+    ui: &mut Ui,
+    id: &'static str,
+    ramp: &mut ColorRamp,
+    palettes: &[Palette],
+) -> bool {
+    let mut changed = false;
+    _ = ComboBox::from_id_source(id)
+        .selected_text(ramp.name())
+        .show_ui(ui, |ui| {
+            changed |= ui
+                .selectable_value(ramp, ColorRamp::Classic, "Classic")
+                .changed();
+            for map in ColorMap::iterator() {
+                changed |= ui
+                    .selectable_value(ramp, ColorRamp::Map(*map), <&'static str>::from(map))
+                    .changed();
+            }
+            for palette in palettes {
+                changed |= ui
+                    .selectable_value(ramp, ColorRamp::Custom(palette.clone()), &palette.name)
+                    .changed();
+            }
+        });
+    changed
+}
@@ -1,15 +1,21 @@
 use {
+    super::world_overlay_selection::color_ramp_selection,
     crate::{
         gui::WindowSystem,
-        planet_renderer::{WorldRenderSettings, WorldView},
-        resources::ShouldRedraw,
+        planet_renderer::WorldRenderSettings,
+        resources::{ShouldRedraw, UserPalettes},
     },
     bevy::ecs::{
         change_detection::Mut,
         system::{SystemParam, SystemState},
         world::World,
     },
-    bevy_egui::egui::Ui,
+    bevy_egui::egui::{ComboBox, DragValue, Ui},
+    planet::{
+        map::{Hillshade, MapColors, WorldView},
+        palette::{BiomePalette, Palette},
+        projection::{MapProjection, Projection},
+    },
     std::marker::PhantomData,
 };
 
@@ -26,7 +32,7 @@ impl WindowSystem for WorldViewSelection<'_, '_> {
             for view in WorldView::iterator() {
                 let view = *view;
                 if ui
-                    .selectable_label(view == current_selection, <&'static str>::from(view))
+                    .selectable_label(view == current_selection, view.label())
                     .clicked()
                     && render_settings.view != view
                 {
@@ -34,6 +40,22 @@ impl WindowSystem for WorldViewSelection<'_, '_> {
                     world.resource_mut::<ShouldRedraw>().0 = true;
                 }
             }
+
+            ui.separator();
+            let projection = render_settings.projection;
+            projection_settings(&mut render_settings.projection, ui);
+            ui.separator();
+            let hillshade = render_settings.style.hillshade;
+            hillshade_settings(&mut render_settings.style.hillshade, ui);
+            ui.separator();
+            let palettes = &world.resource::<UserPalettes>().0;
+            let colors_changed = color_settings(&mut render_settings.style.colors, palettes, ui);
+            if render_settings.projection != projection
+                || render_settings.style.hillshade != hillshade
+                || colors_changed
+            {
+                world.resource_mut::<ShouldRedraw>().0 = true;
+            }
         });
     }
 
@@ -45,3 +67,82 @@ impl WindowSystem for WorldViewSelection<'_, '_> {
         false
     }
 }
+
+fn projection_settings(projection: &mut Projection, ui: &mut Ui) {
+    ComboBox::from_label("Projection")
+        .selected_text(<&'static str>::from(projection.kind))
+        .show_ui(ui, |ui| {
+            for kind in MapProjection::iterator() {
+                _ = ui.selectable_value(&mut projection.kind, *kind, <&'static str>::from(kind));
+            }
+        });
+    ui.horizontal(|ui| {
+        ui.label("Centre longitude");
+        ui.add(
+            DragValue::new(&mut projection.center_longitude)
+                .clamp_range(-180.0..=180.0)
+                .suffix("°"),
+        );
+    });
+    ui.horizontal(|ui| {
+        ui.label("Centre latitude");
+        ui.add_enabled(
+            projection.kind == MapProjection::Orthographic,
+            DragValue::new(&mut projection.center_latitude)
+                .clamp_range(-90.0..=90.0)
+                .suffix("°"),
+        );
+    });
+}
+
+fn hillshade_settings(hillshade: &mut Hillshade, ui: &mut Ui) {
+    _ = ui
+        .checkbox(&mut hillshade.multiply, "Shade relief")
+        .on_hover_text("Multiply the relief view over the other views");
+    ui.horizontal(|ui| {
+        ui.label("Light direction");
+        ui.add(
+            DragValue::new(&mut hillshade.azimuth)
+                .clamp_range(0.0..=360.0)
+                .suffix("°"),
+        );
+    });
+    ui.horizontal(|ui| {
+        ui.label("Light elevation");
+        ui.add(
+            DragValue::new(&mut hillshade.elevation)
+                .clamp_range(0.0..=90.0)
+                .suffix("°"),
+        );
+    });
+    ui.horizontal(|ui| {
+        ui.label("Vertical exaggeration");
+        ui.add(
+            DragValue::new(&mut hillshade.exaggeration)
+                .clamp_range(1.0..=200.0)
+                .suffix("x"),
+        );
+    });
+}
+
+/// Returns whether the colours changed
+fn color_settings(colors: &mut MapColors, palettes: &[Palette], ui: &mut Ui) -> bool {
+    let biomes = colors.biomes;
+    ComboBox::from_label("Biome colours")
+        .selected_text(<&'static str>::from(colors.biomes))
+        .show_ui(ui, |ui| {
+            for palette in BiomePalette::iterator() {
+                _ = ui.selectable_value(
+                    &mut colors.biomes,
+                    *palette,
+                    <&'static str>::from(palette),
+                );
+            }
+        });
+    let mut changed = colors.biomes != biomes;
+    ui.horizontal(|ui| {
+        ui.label("Coastline colours");
+        changed |= color_ramp_selection(ui, "Coastlines", &mut colors.coastlines, palettes);
+    });
+    changed
+}
@@ -17,27 +17,4 @@ macro_rules! iterable_enum {
     }
 }
 #[cfg(feature = "render")]
-macro_rules! iterable_enum_stringify {
-    ($Name:ident { $($Variant:ident),*$(,)? }) =>
-    {
-        crate::macros::iterable_enum!($Name { $($Variant,)* });
-        impl From<$Name> for &'static str {
-            fn from(value: $Name) -> &'static str {
-                match value {
-                    $($Name::$Variant => stringify!($Variant),)*
-                }
-            }
-        }
-        impl From<&$Name> for &'static str {
-            fn from(value: &$Name) -> &'static str {
-                match value {
-                    $($Name::$Variant => stringify!($Variant),)*
-                }
-            }
-        }
-    }
-}
-#[cfg(feature = "render")]
 pub(crate) use iterable_enum;
-#[cfg(feature = "render")]
-pub(crate) use iterable_enum_stringify;
@@ -1,11 +1,12 @@
 #![cfg_attr(not(feature = "logging"), windows_subsystem = "windows")]
 
 use {
-    crate::resources::GenerateWorldTask,
+    crate::resources::{Autosave, GenerateWorldTask},
     futures_lite::future::{block_on, poll_once},
     resources::GenerateWorldProgressChannel,
 };
 
+pub mod cli;
 pub mod components;
 #[cfg(feature = "render")]
 pub mod gui;
@@ -15,7 +16,11 @@ pub mod planet_renderer;
 pub mod plugins;
 pub mod resources;
 
-use {bevy::prelude::*, planet::WorldManager, plugins::WorldPlugins};
+use {
+    bevy::prelude::*,
+    planet::{world_manager::WorldOrigin, WorldManager},
+    plugins::{AutosavePlugin, WorldPlugins},
+};
 #[cfg(feature = "render")]
 use {
     bevy::render::camera::RenderTarget,
@@ -23,9 +28,16 @@ use {
         egui::{FontData, FontDefinitions, FontFamily, ProgressBar},
         EguiContext,
     },
-    gui::{render_windows, widget, widgets::ToolbarWidget, window::open_window, windows::TileInfo},
+    gui::{
+        render_windows,
+        widget,
+        widgets::{LegendWidget, ToolbarWidget},
+        window::open_window,
+        windows::TileInfo,
+    },
     planet_renderer::{WorldRenderSettings, WorldRenderer},
-    resources::{CursorMapPosition, OpenedWindows, ShouldRedraw},
+    plugins::WorldAssetPlugin,
+    resources::{CursorMapPosition, OpenedWindows, ShouldRedraw, UserPalettes},
 };
 #[cfg(all(feature = "render", feature = "logging"))]
 use {
@@ -42,6 +54,7 @@ fn update_cursor_map_position(
     transform: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
     windows: Res<Windows>,
     world_manager: Res<WorldManager>,
+    render_settings: Res<WorldRenderSettings>,
 ) {
     let Some(world) = world_manager.get_world() else {
         return
@@ -65,14 +78,24 @@ fn update_cursor_map_position(
         let world_position =
             ndc_to_world.project_point3(ndc.extend(-1.0)).truncate() / WORLD_SCALE as f32;
 
-        cursor_map_position.x = world.width as i32 / 2 + f32::ceil(world_position.x) as i32 - 1;
-        cursor_map_position.y = world.height as i32 / 2 + f32::ceil(world_position.y) as i32 - 1;
+        // Position in the map image, from its north west corner
+        let image_x = world.width as f32 / 2.0 + world_position.x;
+        let image_y = world.height as f32 / 2.0 - world_position.y;
+        (cursor_map_position.x, cursor_map_position.y) = match render_settings
+            .projection
+            .cell_at(world.width, world.height, image_x, image_y)
+        {
+            Some((x, y)) => (x as i32, y as i32),
+            // Outside of the projected world
+            None => (-1, -1),
+        };
     }
 }
 
 fn handle_generate_world_task(
     mut generate_world_task: ResMut<GenerateWorldTask>,
     mut world_manager: ResMut<WorldManager>,
+    mut autosave: ResMut<Autosave>,
     #[cfg(feature = "render")] mut should_redraw: ResMut<ShouldRedraw>,
     #[cfg(feature = "render")] mut egui_ctx: ResMut<'_, EguiContext>,
     #[cfg(feature = "render")] progress_channel: Res<'_, GenerateWorldProgressChannel>,
@@ -84,7 +107,8 @@ fn handle_generate_world_task(
             if let Some(result) = block_on(poll_once(task)) {
                 match result {
                     Ok(world) => {
-                        world_manager.set_world(world);
+                        world_manager.set_world(world, WorldOrigin::Generated);
+                        autosave.requested = true;
                         #[cfg(feature = "render")]
                         {
                             should_redraw.0 = true;
@@ -200,6 +224,13 @@ fn open_tile_info(mut windows: ResMut<OpenedWindows>, keys: Res<Input<KeyCode>>)
 fn update_gui(world: &mut World) {
     world.resource_scope(|world, mut ctx: Mut<EguiContext>| {
         let ctx = ctx.ctx_mut();
+        if world.resource::<WorldManager>().get_world().is_some() {
+            _ = bevy_egui::egui::SidePanel::right("Legend")
+                .resizable(false)
+                .show(ctx, |ui| {
+                    widget::<LegendWidget<'_, '_>>(world, ui, "Legend".into());
+                });
+        }
         #[cfg(feature = "logging")]
         {
             bevy_egui::egui::CentralPanel::default()
@@ -281,6 +312,15 @@ fn redraw_map(
 #[cfg(feature = "render")]
 const WORLD_SCALE: i32 = 4;
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    match cli::run(std::env::args().skip(1)) {
+        Some(Ok(())) => return Ok(()),
+        Some(Err(err)) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        },
+        None => {},
+    }
+
     let mut app = App::new();
     #[cfg(feature = "render")]
     {
@@ -299,13 +339,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .insert_resource(WorldRenderSettings::default())
             .insert_resource(ShouldRedraw::default())
             .insert_resource(WorldRenderer::default())
+            .insert_resource(UserPalettes::from_config_dir())
             .add_startup_system(generate_graphics)
             .add_system(update_gui)
             .add_system(update_cursor_map_position)
             .add_system(open_tile_info)
             .add_system(redraw_map);
 
-        app.add_plugins(WorldPlugins);
+        app.add_plugins(WorldPlugins).add_plugin(WorldAssetPlugin);
     }
     #[cfg(not(feature = "render"))]
     {
@@ -315,6 +356,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     app.insert_resource(WorldManager::new())
         .insert_resource(GenerateWorldProgressChannel::new())
         .insert_resource(GenerateWorldTask(None))
+        .add_plugin(AutosavePlugin)
         .add_system(handle_generate_world_task)
         .run();
 
@@ -0,0 +1,133 @@
+#[cfg(feature = "render")]
+use crate::{
+    gui::{window::open_window, windows::RestoreAutosave},
+    resources::OpenedWindows,
+};
+use {
+    crate::resources::Autosave,
+    bevy::{app::AppExit, prelude::*, tasks::AsyncComputeTaskPool},
+    directories::ProjectDirs,
+    futures_lite::future::{block_on, poll_once},
+    planet::{
+        saving::Autosaves,
+        world_manager::{SaveError, SaveSizeReport},
+        WorldManager,
+    },
+};
+
+/// Periodically saves the current world to the user data directory, and keeps
+/// the autosave of a session that crashed around to be restored
+pub struct AutosavePlugin;
+
+impl Plugin for AutosavePlugin {
+    fn build(&self, app: &mut App) {
+        let autosaves = ProjectDirs::from("", "", "worlds-history-sim-rs")
+            .map(|directories| Autosaves::new(directories.data_dir().join("autosaves")));
+        if autosaves.is_none() {
+            warn!("No user data directory found, autosave is disabled");
+        }
+        let settings = autosaves
+            .as_ref()
+            .map(Autosaves::load_settings)
+            .unwrap_or_default();
+
+        let restorable = autosaves
+            .as_ref()
+            .and_then(|autosaves| match autosaves.begin_session() {
+                Ok(false) => None,
+                Ok(true) => match autosaves.set_aside_latest() {
+                    Ok(restorable) => restorable,
+                    Err(err) => {
+                        error!("Failed to keep the last session's autosave: {err}");
+                        None
+                    },
+                },
+                Err(err) => {
+                    error!("Failed to mark session as running: {err}");
+                    None
+                },
+            });
+        if let Some(path) = &restorable {
+            warn!(
+                "Last session didn't exit cleanly, its newest autosave was kept at {}",
+                path.display()
+            );
+        }
+
+        _ = app
+            .insert_resource(Autosave {
+                autosaves,
+                timer: Autosave::interval_timer(&settings),
+                settings,
+                requested: false,
+                task: None,
+                restorable,
+            })
+            .add_system(autosave)
+            .add_system_to_stage(CoreStage::Last, end_session);
+        #[cfg(feature = "render")]
+        {
+            _ = app.add_startup_system(offer_restore);
+        }
+    }
+}
+
+fn autosave(mut autosave: ResMut<Autosave>, time: Res<Time>, world_manager: Res<WorldManager>) {
+    if let Some(task) = &mut autosave.task {
+        if !task.is_finished() {
+            return;
+        }
+        if let Some(result) = block_on(poll_once(task)) {
+            log_autosave(result);
+        }
+        autosave.task = None;
+    }
+
+    if !autosave.settings.enabled {
+        return;
+    }
+    let due = autosave.timer.tick(time.delta()).just_finished();
+    if !(due || autosave.requested) || world_manager.get_world().is_none() {
+        return;
+    }
+    autosave.requested = false;
+
+    let Some(autosaves) = autosave.autosaves.clone() else {
+        return;
+    };
+    // Writing a large world takes a while, so a copy of it is saved in the
+    // background instead of stalling the frame
+    let snapshot = world_manager.clone();
+    let slots = autosave.settings.slots;
+    autosave.task =
+        Some(AsyncComputeTaskPool::get().spawn(async move { autosaves.write(&snapshot, slots) }));
+}
+
+fn log_autosave(result: Result<SaveSizeReport, SaveError>) {
+    match result {
+        Ok(report) => debug!("Autosaved world ({report})"),
+        Err(err) => error!("Failed to autosave: {err}"),
+    }
+}
+
+fn end_session(mut exit_events: EventReader<AppExit>, mut autosave: ResMut<Autosave>) {
+    if exit_events.iter().next().is_none() {
+        return;
+    }
+    // Let a running autosave finish, so its slots aren't left half rotated
+    if let Some(task) = autosave.task.take() {
+        log_autosave(block_on(task));
+    }
+    if let Some(autosaves) = &autosave.autosaves {
+        if let Err(err) = autosaves.end_session() {
+            error!("Failed to mark session as ended: {err}");
+        }
+    }
+}
+
+#[cfg(feature = "render")]
+fn offer_restore(autosave: Res<Autosave>, mut windows: ResMut<OpenedWindows>) {
+    if autosave.restorable.is_some() {
+        open_window::<RestoreAutosave>(&mut windows);
+    }
+}
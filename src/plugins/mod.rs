@@ -1,2 +1,8 @@
 pub mod world_plugins;
 pub use world_plugins::WorldPlugins;
+pub mod autosave_plugin;
+pub use autosave_plugin::AutosavePlugin;
+#[cfg(feature = "render")]
+pub mod world_asset_plugin;
+#[cfg(feature = "render")]
+pub use world_asset_plugin::{SaveAssetIoPlugin, WorldAsset, WorldAssetPlugin};
@@ -0,0 +1,224 @@
+use {
+    crate::resources::{LoadWorldProgressChannel, LoadWorldTask, ShouldRedraw},
+    bevy::{
+        asset::{
+            AssetIo,
+            AssetIoError,
+            AssetLoader,
+            Error,
+            LoadContext,
+            LoadState,
+            LoadedAsset,
+            Metadata,
+        },
+        prelude::*,
+        reflect::TypeUuid,
+        utils::BoxedFuture,
+    },
+    bevy_egui::{
+        egui::{Color32, ProgressBar, TopBottomPanel},
+        EguiContext,
+    },
+    crossbeam_channel::Sender,
+    planet::{
+        saving::{SaveFormat, WorldInfo},
+        world_manager::LoadError,
+        World,
+        WorldManager,
+    },
+    std::{
+        ffi::OsStr,
+        fs::File,
+        io::BufReader,
+        path::{Path, PathBuf},
+    },
+};
+
+/// A world decoded from a save by the asset server. Failures are kept in the
+/// asset, as the asset server would only log them.
+#[derive(TypeUuid)]
+#[uuid = "8b1d5e3c-4f0a-4c5e-9a4e-2f7c1d0b6e93"]
+pub struct WorldAsset(pub Result<(World, WorldInfo), LoadError>);
+
+fn is_save(path: &Path) -> bool {
+    path.extension() == Some(OsStr::new(SaveFormat::Binary.extension()))
+}
+
+/// Asset I/O that leaves saves to the [`WorldAssetLoader`] to read, so they
+/// are streamed from their file instead of being read into memory as a whole
+pub struct SaveAssetIo(Box<dyn AssetIo>);
+
+impl AssetIo for SaveAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        if is_save(path) {
+            Box::pin(async { Ok(vec![]) })
+        } else {
+            self.0.load_path(path)
+        }
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        self.0.read_directory(path)
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        self.0.get_metadata(path)
+    }
+
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        self.0.watch_path_for_changes(path)
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.0.watch_for_changes()
+    }
+}
+
+/// Installs the [`SaveAssetIo`], has to be added before `AssetPlugin`
+pub struct SaveAssetIoPlugin;
+
+impl Plugin for SaveAssetIoPlugin {
+    fn build(&self, app: &mut App) {
+        let asset_io = AssetPlugin::default().create_platform_default_asset_io();
+        _ = app.insert_resource(AssetServer::new(SaveAssetIo(asset_io)));
+    }
+}
+
+/// Decodes `.rsplnt` saves on the asset server's task pool, reporting progress
+/// to the [`LoadWorldProgressChannel`]
+pub struct WorldAssetLoader {
+    progress_sender: Sender<(f32, String)>,
+}
+
+impl AssetLoader for WorldAssetLoader {
+    fn load<'a>(
+        &'a self,
+        _bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let result = File::open(load_context.path())
+                .map_err(LoadError::MissingSave)
+                .and_then(|file| {
+                    WorldManager::read_save(BufReader::new(file), &self.progress_sender)
+                });
+            load_context.set_default_asset(LoadedAsset::new(WorldAsset(result)));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["rsplnt"]
+    }
+}
+
+/// Loads saves without blocking the UI, see [`load_world`]
+pub struct WorldAssetPlugin;
+
+impl Plugin for WorldAssetPlugin {
+    fn build(&self, app: &mut App) {
+        let progress_channel = LoadWorldProgressChannel::new();
+        _ = app
+            .add_asset::<WorldAsset>()
+            .add_asset_loader(WorldAssetLoader {
+                progress_sender: progress_channel.sender(),
+            })
+            .insert_resource(progress_channel)
+            .insert_resource(LoadWorldTask::default())
+            .add_system(handle_load_world_task)
+            .add_system(show_load_world_progress.after(handle_load_world_task));
+    }
+}
+
+/// Starts loading a save in the background. Once it is loaded, it replaces
+/// the current world.
+pub fn load_world(asset_server: &AssetServer, load_world_task: &mut LoadWorldTask, path: &Path) {
+    if load_world_task.handle.is_some() {
+        debug!("Already loading a world");
+        return;
+    }
+    load_world_task.error = None;
+
+    // Paths are relative to the asset folder, unless they are absolute
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let handle = asset_server.load(path.as_path());
+    // The asset server doesn't load a path again once it was loaded, even
+    // though the loaded world was taken out of the assets
+    if asset_server.get_load_state(&handle) == LoadState::Loaded {
+        asset_server.reload_asset(path.as_path());
+    }
+    load_world_task.handle = Some(handle);
+}
+
+fn handle_load_world_task(
+    mut load_world_task: ResMut<LoadWorldTask>,
+    mut world_assets: ResMut<Assets<WorldAsset>>,
+    asset_server: Res<AssetServer>,
+    mut world_manager: ResMut<WorldManager>,
+    mut should_redraw: ResMut<ShouldRedraw>,
+) {
+    let Some(handle) = &load_world_task.handle else {
+        return;
+    };
+
+    if asset_server.get_load_state(handle) == LoadState::Failed {
+        let error = match asset_server.get_handle_path(handle) {
+            Some(path) => format!("Failed to load {}", path.path().display()),
+            None => String::from("Failed to load world"),
+        };
+        error!("{error}");
+        load_world_task.error = Some(error);
+        load_world_task.handle = None;
+    } else if let Some(WorldAsset(result)) = world_assets.remove(handle) {
+        match result {
+            Ok((world, info)) => {
+                #[cfg(feature = "logging")]
+                debug!("Done loading world");
+                world_manager.set_loaded_world(world, info);
+                should_redraw.0 = true;
+            },
+            Err(err) => {
+                error!("Failed to load: {err}");
+                load_world_task.error = Some(format!("Failed to load world: {err}"));
+            },
+        }
+        load_world_task.handle = None;
+    }
+}
+
+fn show_load_world_progress(
+    mut load_world_task: ResMut<LoadWorldTask>,
+    mut egui_ctx: ResMut<'_, EguiContext>,
+    progress_channel: Res<'_, LoadWorldProgressChannel>,
+    mut progress: Local<(f32, String)>,
+) {
+    if let Some(error) = &load_world_task.error {
+        let mut dismissed = false;
+        _ = TopBottomPanel::bottom("Loading World Error").show(egui_ctx.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.colored_label(Color32::LIGHT_RED, error.as_str());
+                dismissed = ui.button("Dismiss").clicked();
+            });
+        });
+        if dismissed {
+            load_world_task.error = None;
+        }
+    }
+
+    if load_world_task.handle.is_none() {
+        *progress = (0.0, String::from("Loading world..."));
+        return;
+    }
+
+    if let Ok(new_progress) = progress_channel.receiver().try_recv() {
+        *progress = new_progress;
+    }
+    _ = TopBottomPanel::bottom("Loading World ProgressBar")
+        .default_height(8.0)
+        .show(egui_ctx.ctx_mut(), |ui| {
+            ui.add(ProgressBar::new(progress.0).text(progress.1.as_str()));
+        });
+}
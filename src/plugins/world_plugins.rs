@@ -13,6 +13,7 @@ use bevy::{
 };
 #[cfg(feature = "render")]
 use {
+    crate::plugins::SaveAssetIoPlugin,
     bevy::{
         asset::AssetPlugin,
         core_pipeline::CorePipelinePlugin,
@@ -56,6 +57,8 @@ impl PluginGroup for WorldPlugins {
                     },
                     ..default()
                 })
+                // Inserts the asset server, which `AssetPlugin` then keeps
+                .add(SaveAssetIoPlugin)
                 .add(AssetPlugin::default())
                 .add(RenderPlugin)
                 .add(ImagePlugin::default_nearest())
@@ -1,9 +1,25 @@
 #[cfg(feature = "render")]
-use {crate::gui::WindowId, bevy::utils::HashSet, std::fmt::Display};
 use {
-    bevy::{prelude::Resource, tasks::Task},
+    crate::{gui::WindowId, plugins::WorldAsset},
+    bevy::{asset::Handle, log::error, utils::HashSet},
+    directories::ProjectDirs,
+    planet::{palette::Palette, saving::SaveMetadata, world_manager::LoadError},
+    std::fmt::Display,
+};
+use {
+    bevy::{
+        prelude::Resource,
+        tasks::Task,
+        time::{Timer, TimerMode},
+    },
     crossbeam_channel::{bounded, Receiver, Sender},
-    planet::{World, WorldGenError},
+    planet::{
+        saving::{AutosaveSettings, Autosaves},
+        world_manager::{SaveError, SaveSizeReport},
+        World,
+        WorldGenError,
+    },
+    std::{marker::PhantomData, path::PathBuf},
 };
 
 #[cfg(feature = "render")]
@@ -43,10 +59,43 @@ impl OpenedWindows {
     }
 }
 
+/// Palettes loaded from a palette file, offered next to the named colour maps
+#[cfg(feature = "render")]
+#[derive(Default, Resource)]
+pub struct UserPalettes(pub Vec<Palette>);
+
+#[cfg(feature = "render")]
+impl UserPalettes {
+    /// Reads the palette file in the user config directory, if there is one
+    pub fn from_config_dir() -> Self {
+        let Some(path) = ProjectDirs::from("", "", "worlds-history-sim-rs")
+            .map(|directories| directories.config_dir().join(Palette::FILE_NAME))
+        else {
+            return UserPalettes::default();
+        };
+        if !path.is_file() {
+            return UserPalettes::default();
+        }
+        match Palette::load(&path) {
+            Ok(palettes) => UserPalettes(palettes),
+            Err(err) => {
+                error!("Failed to load palettes from {}: {err}", path.display());
+                UserPalettes::default()
+            },
+        }
+    }
+}
+
+/// Progress reported by a task running in the background, `T` tells apart the
+/// tasks that report progress
 #[derive(Resource)]
-pub struct GenerateWorldProgressChannel(Sender<(f32, String)>, Receiver<(f32, String)>);
+pub struct ProgressChannel<T>(
+    Sender<(f32, String)>,
+    Receiver<(f32, String)>,
+    PhantomData<fn() -> T>,
+);
 
-impl GenerateWorldProgressChannel {
+impl<T> ProgressChannel<T> {
     pub fn new() -> Self {
         bounded(1).into()
     }
@@ -59,15 +108,56 @@ impl GenerateWorldProgressChannel {
         &self.1
     }
 }
-impl Default for GenerateWorldProgressChannel {
+impl<T> Default for ProgressChannel<T> {
     fn default() -> Self {
         Self::new()
     }
 }
-impl From<(Sender<(f32, String)>, Receiver<(f32, String)>)> for GenerateWorldProgressChannel {
+impl<T> From<(Sender<(f32, String)>, Receiver<(f32, String)>)> for ProgressChannel<T> {
     fn from(value: (Sender<(f32, String)>, Receiver<(f32, String)>)) -> Self {
-        Self(value.0, value.1)
+        Self(value.0, value.1, PhantomData)
     }
 }
+pub type GenerateWorldProgressChannel = ProgressChannel<GenerateWorldTask>;
 #[derive(Default, Resource)]
 pub struct GenerateWorldTask(pub Option<Task<Result<World, WorldGenError>>>);
+
+#[cfg(feature = "render")]
+pub type LoadWorldProgressChannel = ProgressChannel<LoadWorldTask>;
+/// Save being loaded by the asset server, see
+/// [`crate::plugins::WorldAssetPlugin`]
+#[cfg(feature = "render")]
+#[derive(Default, Resource)]
+pub struct LoadWorldTask {
+    pub handle: Option<Handle<WorldAsset>>,
+    /// Why the last save failed to load, shown until it's dismissed
+    pub error:  Option<String>,
+}
+/// Metadata of a save being read in the background, to preview it before it
+/// is loaded. See [`planet::WorldManager::peek_save`].
+#[cfg(feature = "render")]
+pub type PeekSaveTask = Task<Result<Option<SaveMetadata>, LoadError>>;
+
+#[derive(Resource)]
+pub struct Autosave {
+    /// `None` if there is no user data directory to autosave to
+    pub autosaves:  Option<Autosaves>,
+    pub settings:   AutosaveSettings,
+    pub timer:      Timer,
+    /// Autosave on the next frame regardless of the timer, e.g. after a world
+    /// was generated
+    pub requested:  bool,
+    /// Autosave being written in the background
+    pub task:       Option<Task<Result<SaveSizeReport, SaveError>>>,
+    /// Newest autosave of a session that didn't exit cleanly
+    pub restorable: Option<PathBuf>,
+}
+impl Autosave {
+    #[must_use]
+    pub fn interval_timer(settings: &AutosaveSettings) -> Timer {
+        Timer::from_seconds(
+            settings.interval_minutes.max(1) as f32 * 60.0,
+            TimerMode::Repeating,
+        )
+    }
+}